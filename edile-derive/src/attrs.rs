@@ -0,0 +1,113 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Error, Expr, Field, Ident, Path, Result, Token};
+
+/// A single argument of an `#[edile(...)]` attribute.
+enum Arg {
+    /// `default` or `default = expr`.
+    Default(Ident, Option<Expr>),
+    /// `post_init = path`.
+    PostInit(Ident, Path),
+}
+
+impl Parse for Arg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident = input.parse::<Ident>()?;
+        if ident == "default" {
+            if input.peek(Token![=]) {
+                input.parse::<Token![=]>()?;
+                Ok(Arg::Default(ident, Some(input.parse()?)))
+            } else {
+                Ok(Arg::Default(ident, None))
+            }
+        } else if ident == "post_init" {
+            input.parse::<Token![=]>()?;
+            Ok(Arg::PostInit(ident, input.parse()?))
+        } else {
+            Err(Error::new(
+                ident.span(),
+                format!("unknown `edile` attribute `{}`", ident),
+            ))
+        }
+    }
+}
+
+/// Parses the arguments of all the `#[edile(...)]` attributes in `attrs`.
+fn parse_args(attrs: &[Attribute]) -> Result<Vec<Arg>> {
+    let mut args = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("edile")) {
+        args.extend(attr.parse_args_with(Punctuated::<Arg, Token![,]>::parse_terminated)?);
+    }
+    Ok(args)
+}
+
+/// The `#[edile(...)]` attributes of a field.
+#[derive(Default)]
+pub struct FieldAttrs {
+    /// The expression used to initialize the field if it has a `default` attribute.
+    pub default: Option<TokenStream>,
+}
+
+impl FieldAttrs {
+    pub fn parse(field: &Field) -> Result<Self> {
+        let mut this = Self::default();
+        for arg in parse_args(&field.attrs)? {
+            match arg {
+                Arg::Default(ident, _) if this.default.is_some() => {
+                    return Err(Error::new(ident.span(), "duplicate `default` attribute"));
+                }
+                Arg::Default(_, expr) => {
+                    let expr = expr.map_or_else(
+                        || quote!(::core::default::Default::default()),
+                        |expr| quote!(#expr),
+                    );
+                    this.default = Some(expr);
+                }
+                Arg::PostInit(ident, _) => {
+                    return Err(Error::new(
+                        ident.span(),
+                        "`post_init` can only be applied to the whole type",
+                    ));
+                }
+            }
+        }
+        Ok(this)
+    }
+}
+
+/// Returns whether the given field has a `default` attribute.
+///
+/// This assumes the attributes have already been validated by [`FieldAttrs::parse`].
+pub fn has_default(field: &Field) -> bool {
+    FieldAttrs::parse(field).is_ok_and(|attrs| attrs.default.is_some())
+}
+
+/// The `#[edile(...)]` attributes of a struct or an enum.
+#[derive(Default)]
+pub struct TypeAttrs {
+    /// The function to call on the `Init` of the type after it has been initialized.
+    pub post_init: Option<Path>,
+}
+
+impl TypeAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut this = Self::default();
+        for arg in parse_args(attrs)? {
+            match arg {
+                Arg::PostInit(ident, _) if this.post_init.is_some() => {
+                    return Err(Error::new(ident.span(), "duplicate `post_init` attribute"));
+                }
+                Arg::PostInit(_, path) => this.post_init = Some(path),
+                Arg::Default(ident, _) => {
+                    return Err(Error::new(
+                        ident.span(),
+                        "`default` can only be applied to fields",
+                    ));
+                }
+            }
+        }
+        Ok(this)
+    }
+}
@@ -0,0 +1,174 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DataEnum, DeriveInput, Error, Fields, Ident, Result};
+
+use crate::attrs::has_default;
+use crate::utils::{filter_fields, members, variant_method};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    match &input.data {
+        Data::Struct(data) => Ok(derive_struct(&input, &data.fields)),
+        Data::Enum(data) => Ok(derive_enum(&input, data)),
+        Data::Union(data) => Err(Error::new_spanned(
+            data.union_token,
+            "unions can't be initialized field by field",
+        )),
+    }
+}
+
+/// Returns the names of the parameters of the generated constructors, one for each field.
+fn params(fields: &Fields) -> Vec<Ident> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(idx, f)| match &f.ident {
+            Some(ident) => ident.clone(),
+            None => format_ident!("field{}", idx),
+        })
+        .collect()
+}
+
+/// The constructors generated for some fields.
+struct Ctors {
+    /// The parameters of the constructor that takes a constructor for each field.
+    ctor_params: TokenStream,
+    /// The parameters of the constructor that takes a value for each field.
+    value_params: TokenStream,
+    /// The expression initializing the projection `__edile_proj` of the fields with the
+    /// constructors passed as parameters.
+    ctor_init: TokenStream,
+    /// The expression initializing the projection `__edile_proj` of the fields with the
+    /// values passed as parameters.
+    value_init: TokenStream,
+}
+
+/// Generates the parameters and the body of the constructors for `fields`, given the
+/// name of their `InitProj`. If `fields` is empty their projection is assumed to be `()`.
+fn ctors(init_proj: &Ident, fields: &Fields) -> Ctors {
+    let members = members(fields);
+    let params = params(fields);
+    let tys = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+
+    let init = |method: TokenStream| match fields.is_empty() {
+        true => quote!(()),
+        false => quote!(#init_proj { #( #members: __edile_proj.#members.#method(#params), )* }),
+    };
+
+    Ctors {
+        ctor_params: quote!(#( #params: ::edile::ctor!(#tys), )*),
+        value_params: quote!(#( #params: #tys, )*),
+        ctor_init: init(quote!(init_with)),
+        value_init: init(quote!(init)),
+    }
+}
+
+fn derive_struct(input: &DeriveInput, fields: &Fields) -> TokenStream {
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let init_proj = format_ident!("{}InitProj", ident);
+
+    let Ctors {
+        ctor_params,
+        value_params,
+        ctor_init,
+        value_init,
+    } = ctors(&init_proj, &filter_fields(fields, |f| !has_default(f)).0);
+    let ctor_doc = format!(
+        "Creates a constructor for `{}` which initializes each field with the given constructor.",
+        ident
+    );
+    let value_doc = format!(
+        "Creates a constructor for `{}` which initializes each field with the given value.",
+        ident
+    );
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #[doc = #ctor_doc]
+            #[allow(clippy::too_many_arguments)]
+            #vis fn new_ctor(#ctor_params) -> ::edile::ctor!(Self) {
+                ::edile::proj_fn::<Self, _>(move |_, __edile_proj| #ctor_init)
+            }
+
+            #[doc = #value_doc]
+            #[allow(clippy::too_many_arguments)]
+            #vis fn new_value_ctor(#value_params) -> ::edile::ctor!(Self) {
+                ::edile::proj_fn::<Self, _>(move |_, __edile_proj| #value_init)
+            }
+        }
+    }
+}
+
+fn derive_enum(input: &DeriveInput, data: &DataEnum) -> TokenStream {
+    let vis = &input.vis;
+    let ident = &input.ident;
+
+    let mut methods = TokenStream::new();
+    for variant in &data.variants {
+        let variant_ident = &variant.ident;
+        let method = variant_method(variant_ident);
+
+        if variant.fields.is_empty() {
+            let ctor = format_ident!("{}_ctor", method);
+            let ctor_doc = format!(
+                "Creates a constructor for the `{}` variant of `{}`.",
+                variant_ident, ident
+            );
+            methods.extend(quote! {
+                #[doc = #ctor_doc]
+                #vis fn #ctor() -> ::edile::ctor!(Self) {
+                    ::edile::proj_fn::<Self, _>(|_, __edile_proj| __edile_proj.#method())
+                }
+            });
+            continue;
+        }
+
+        let init_proj = format_ident!("{}{}InitProj", ident, variant_ident);
+        let Ctors {
+            ctor_params,
+            value_params,
+            ctor_init,
+            value_init,
+        } = ctors(&init_proj, &variant.fields);
+        let ctor = format_ident!("{}_ctor", method);
+        let value_ctor = format_ident!("{}_value_ctor", method);
+        let ctor_doc = format!(
+            "Creates a constructor for the `{}` variant of `{}` which initializes each field \
+            with the given constructor.",
+            variant_ident, ident
+        );
+        let value_doc = format!(
+            "Creates a constructor for the `{}` variant of `{}` which initializes each field \
+            with the given value.",
+            variant_ident, ident
+        );
+
+        methods.extend(quote! {
+            #[doc = #ctor_doc]
+            #[allow(clippy::too_many_arguments)]
+            #vis fn #ctor(#ctor_params) -> ::edile::ctor!(Self) {
+                ::edile::proj_fn::<Self, _>(move |_, __edile_proj| {
+                    __edile_proj.#method(move |__edile_proj| #ctor_init)
+                })
+            }
+
+            #[doc = #value_doc]
+            #[allow(clippy::too_many_arguments)]
+            #vis fn #value_ctor(#value_params) -> ::edile::ctor!(Self) {
+                ::edile::proj_fn::<Self, _>(move |_, __edile_proj| {
+                    __edile_proj.#method(move |__edile_proj| #value_init)
+                })
+            }
+        });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            #methods
+        }
+    }
+}
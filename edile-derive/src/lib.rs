@@ -1,3 +1,130 @@
-// TODO: Write basic projecting macro
-// TODO: Write pin projecting macro
-// TODO: Maybe write Own partial move macro
+extern crate proc_macro;
+
+mod attrs;
+mod ctor;
+mod own;
+mod pin;
+mod project;
+mod utils;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Derives `ProjConstruct` for a struct, allowing it to be initialized
+/// field by field with `proj_fn`.
+///
+/// For a struct `Foo` this generates a `FooUninitProj<'a>` struct, whose fields
+/// are the `Uninit<'a, _>` of the fields of `Foo`, and a `FooInitProj<'a>` struct,
+/// whose fields are the corresponding `Init<'a, _>`. Both have the same visibility
+/// as `Foo` and their fields have the same visibility as the fields of `Foo`.
+///
+/// Tuple structs get tuple struct projections, while structs without fields
+/// are projected to `()`.
+///
+/// The projections carry the generic parameters and the `where` clause of `Foo`
+/// that their fields use. If `Foo` already has a lifetime named `'a` the projections
+/// use `'a0`, `'a1`, ... instead.
+///
+/// Unions and `#[repr(packed)]` structs are rejected, since the fields of the latter
+/// may be unaligned. This applies to the other projection derives too.
+///
+/// Enums must have a primitive representation, e.g. `#[repr(u8)]`. For an enum `Foo`
+/// the `FooUninitProj<'a>` has a method for each variant, e.g. `variant_bar` for the
+/// variant `Bar`, which writes the discriminant and initializes the fields of the
+/// variant with a closure taking a `FooBarUninitProj<'b>` and returning a
+/// `FooBarInitProj<'b>`. Variants without fields don't take a closure.
+///
+/// # Attributes
+///
+/// - `#[edile(default)]` on a field of a struct excludes it from the projections and
+///   initializes it with `Default::default()` after the other fields.
+/// - `#[edile(default = expr)]` does the same but initializes the field with `expr`.
+/// - `#[edile(post_init = path)]` on the type calls `path` with a `&mut Init<'_, Self>`
+///   once the value has been fully initialized, before it is returned.
+#[proc_macro_derive(ProjConstruct, attributes(edile))]
+pub fn derive_proj_construct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    project::derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Derives `ProjMut` for a struct or an enum, allowing its fields to be mutably
+/// borrowed at the same time with `project_mut`.
+///
+/// For a struct `Foo` this generates a `FooMutProj<'a>` with the same shape as `Foo`
+/// whose fields are the `&'a mut _` of the fields of `Foo`, following the same rules
+/// as the `ProjConstruct` derive. For an enum `Foo` the `FooMutProj<'a>` is an enum
+/// with the same variants, and enums don't need a primitive representation.
+#[proc_macro_derive(ProjMut)]
+pub fn derive_proj_mut(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    project::derive_mut(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Derives constructors for a struct or an enum that also derives `ProjConstruct`.
+///
+/// For a struct `Foo` this generates:
+/// - `Foo::new_ctor`, which takes a constructor, i.e. an `impl FnOnce(Uninit<'_, T>) -> Init<'_, T>`,
+///   for each field and returns a constructor for `Foo` that uses them to initialize each field;
+/// - `Foo::new_value_ctor`, which takes a value for each field instead.
+///
+/// The parameters are named after the fields, or `field0`, `field1`, ... for tuple structs.
+/// Fields with a default value, see `ProjConstruct`, don't get a parameter.
+///
+/// For enums the same constructors are generated for each variant, prefixed with the name
+/// of the variant's `UninitProj` method, e.g. `variant_bar_ctor` and `variant_bar_value_ctor`
+/// for a variant `Bar`. Variants without fields only get the former.
+///
+/// The constructors have the same visibility as the type.
+#[proc_macro_derive(Ctor, attributes(edile))]
+pub fn derive_ctor(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    ctor::derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Derives `PinProjConstruct` for a struct, allowing it to be initialized
+/// field by field in a place where it will be pinned with `pin_proj_fn`.
+///
+/// Fields marked with `#[pin]` are structurally pinned: for a struct `Foo` the generated
+/// `FooPinUninitProj<'a>` projects them to `PinUninit<'a, _>`s, while the other fields
+/// are projected to `Uninit<'a, _>`s. Likewise the generated `FooPinInitProj<'a>` expects
+/// `Pin<Init<'a, _>>`s for the pinned fields and `Init<'a, _>`s for the others.
+///
+/// `Foo` will implement `Unpin` only if all its structurally pinned fields do.
+///
+/// This also derives `PinProjMut`, generating a `FooPinMutProj<'a>` with the same shape
+/// as `Foo` whose fields are `Pin<&'a mut _>`s for the pinned fields and `&'a mut _`s
+/// for the others.
+///
+/// `Foo` can't implement `Drop`, since it would allow moving out of the pinned fields.
+/// It can instead be marked with `#[pin(PinnedDrop)]` and implement `PinnedDrop`,
+/// whose `drop` receives a `Pin<&mut Foo>` and a `PinnedDropToken`. Implementing
+/// `PinnedDrop` without the attribute, or vice versa, is a compile error.
+#[proc_macro_derive(PinProjConstruct, attributes(pin))]
+pub fn derive_pin_proj_construct(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    pin::derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Derives `ProjMove` for a struct, allowing an `Own` of it to be destructured
+/// with `Own::into_proj`.
+///
+/// For a struct `Foo` this generates a `FooOwnProj<'a>` struct, whose fields are
+/// the `Own<'a, _>` of the fields of `Foo`, following the same rules as the
+/// `ProjConstruct` derive.
+///
+/// Types that implement `Drop` are rejected, since destructuring them would skip their `drop`.
+#[proc_macro_derive(ProjMove)]
+pub fn derive_proj_move(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    own::derive(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
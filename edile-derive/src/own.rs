@@ -0,0 +1,93 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Error, Fields, Result};
+
+use crate::utils::{
+    assert_not_packed, check_not_packed, members, proj_generics, proj_lifetime, proj_struct,
+    with_lifetime,
+};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    match &input.data {
+        Data::Struct(data) => derive_struct(&input, &data.fields),
+        Data::Enum(data) => Err(Error::new_spanned(
+            data.enum_token,
+            "fields can't be moved out of enums",
+        )),
+        Data::Union(data) => Err(Error::new_spanned(
+            data.union_token,
+            "fields can't be moved out of unions",
+        )),
+    }
+}
+
+fn derive_struct(input: &DeriveInput, fields: &Fields) -> Result<TokenStream> {
+    check_not_packed(input)?;
+    let ident = &input.ident;
+    let own_proj = format_ident!("{}OwnProj", ident);
+    let own_doc = format!("Projection field by field of an `Own<'a, {}>`.", ident);
+    let lt = proj_lifetime(&input.generics, "a");
+    let lt_generics = with_lifetime(&input.generics, &lt);
+    let (lt_impl_generics, _, _) = lt_generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Structs without fields are trivially projected to `()`.
+    let (proj_def, own_proj_ty, own_proj_expr) = if fields.is_empty() {
+        (quote!(), quote!(()), quote!(()))
+    } else {
+        let generics = proj_generics(&input.generics, &lt, fields);
+        let (_, proj_ty_generics, _) = generics.split_for_impl();
+        let def = proj_struct(
+            &input.vis,
+            None,
+            (&own_proj, &own_doc),
+            &generics,
+            fields,
+            |f| {
+                let ty = &f.ty;
+                quote!(::edile::Own<#lt, #ty>)
+            },
+        );
+        let members = members(fields);
+        let expr = quote! {
+            #own_proj {
+                #( #members: ::edile::Own::from_raw(::core::ptr::addr_of_mut!((*ptr).#members)), )*
+            }
+        };
+        (def, quote!(#own_proj #proj_ty_generics), expr)
+    };
+
+    let not_packed = assert_not_packed(input, fields);
+
+    Ok(quote! {
+        #proj_def
+        #not_packed
+
+        impl #lt_impl_generics ::edile::project::WithOwnProj<#lt> for #ident #ty_generics
+        #where_clause
+        {
+            type OwnProj = #own_proj_ty;
+        }
+
+        impl #impl_generics ::edile::project::ProjMove for #ident #ty_generics #where_clause {
+            #[allow(unused_variables, unused_unsafe, clippy::unused_unit)]
+            fn proj_move(
+                this: ::edile::Own<'_, Self>,
+            ) -> ::edile::project::OwnProj<'_, Self> {
+                let ptr: *mut Self = ::edile::Own::leak(this);
+                // SAFETY: `ptr` comes from a reference, so it's valid and the field ones are
+                // valid too. The data was owned by `this`, which we leaked, so the ownership
+                // of each field can be transferred to the new `Own`s.
+                unsafe { #own_proj_expr }
+            }
+        }
+
+        // Moving fields out of a type that implements `Drop` would skip its `drop`.
+        const _: () = {
+            trait ProjMoveIsIncompatibleWithDrop {}
+            #[allow(drop_bounds)]
+            impl<T: ::core::ops::Drop> ProjMoveIsIncompatibleWithDrop for T {}
+            impl #impl_generics ProjMoveIsIncompatibleWithDrop for #ident #ty_generics #where_clause {}
+        };
+    })
+}
@@ -0,0 +1,321 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Data, DeriveInput, Error, Field, Fields, Ident, Lifetime, Result};
+
+use crate::utils::{
+    assert_not_packed, check_not_packed, members, proj_generics, proj_lifetime, proj_struct,
+    with_lifetime,
+};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    match &input.data {
+        Data::Struct(data) => derive_struct(&input, &data.fields),
+        Data::Enum(data) => Err(Error::new_spanned(
+            data.enum_token,
+            "enums can't be pin projected",
+        )),
+        Data::Union(data) => Err(Error::new_spanned(
+            data.union_token,
+            "unions can't be projected field by field",
+        )),
+    }
+}
+
+/// Checks that the `#[pin]` attributes of the given field are well formed.
+fn check_pin_attrs(field: &Field) -> Result<()> {
+    let mut pin_attrs = field.attrs.iter().filter(|attr| attr.path.is_ident("pin"));
+    if let Some(attr) = pin_attrs.next() {
+        if !attr.tokens.is_empty() {
+            return Err(Error::new_spanned(
+                attr,
+                "`#[pin]` doesn't take any argument",
+            ));
+        }
+    }
+    if let Some(attr) = pin_attrs.next() {
+        return Err(Error::new_spanned(attr, "duplicate `#[pin]` attribute"));
+    }
+    Ok(())
+}
+
+/// Returns whether the struct has a `#[pin(PinnedDrop)]` attribute, checking that
+/// its `#[pin]` attributes are well formed.
+fn has_pinned_drop(input: &DeriveInput) -> Result<bool> {
+    let mut pin_attrs = input.attrs.iter().filter(|attr| attr.path.is_ident("pin"));
+    let attr = match pin_attrs.next() {
+        Some(attr) => attr,
+        None => return Ok(false),
+    };
+    if let Some(attr) = pin_attrs.next() {
+        return Err(Error::new_spanned(attr, "duplicate `#[pin]` attribute"));
+    }
+    match attr.parse_args::<Ident>() {
+        Ok(arg) if arg == "PinnedDrop" => Ok(true),
+        _ => Err(Error::new_spanned(
+            attr,
+            "expected `#[pin(PinnedDrop)]` on the struct",
+        )),
+    }
+}
+
+/// Returns whether the given field is structurally pinned, i.e. it has a `#[pin]` attribute.
+fn is_pinned(field: &Field) -> bool {
+    field.attrs.iter().any(|attr| attr.path.is_ident("pin"))
+}
+
+fn derive_struct(input: &DeriveInput, fields: &Fields) -> Result<TokenStream> {
+    check_not_packed(input)?;
+    let ident = &input.ident;
+    let uninit_proj = format_ident!("{}PinUninitProj", ident);
+    let init_proj = format_ident!("{}PinInitProj", ident);
+    let uninit_doc = format!("Projection field by field of a `PinUninit<'a, {}>`.", ident);
+    let init_doc = format!("Projection field by field of a `Pin<Init<'a, {}>>`.", ident);
+    let lt = proj_lifetime(&input.generics, "a");
+    let lt_generics = with_lifetime(&input.generics, &lt);
+    let (lt_impl_generics, _, _) = lt_generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let pinned_drop = has_pinned_drop(input)?;
+    fields.iter().try_for_each(check_pin_attrs)?;
+    let pinned_tys = fields
+        .iter()
+        .filter(|f| is_pinned(f))
+        .map(|f| &f.ty)
+        .collect::<Vec<_>>();
+    let pinned_names = (0..pinned_tys.len()).map(|idx| format_ident!("__field{}", idx));
+
+    // Structs without fields are trivially projected to `()`.
+    let (proj_defs, uninit_proj_ty, init_proj_ty, uninit_proj_expr) = if fields.is_empty() {
+        (quote!(), quote!(()), quote!(()), quote!(()))
+    } else {
+        let generics = proj_generics(&input.generics, &lt, fields);
+        let (_, proj_ty_generics, _) = generics.split_for_impl();
+        let uninit_def = proj_struct(
+            &input.vis,
+            None,
+            (&uninit_proj, &uninit_doc),
+            &generics,
+            fields,
+            |f| {
+                let ty = &f.ty;
+                match is_pinned(f) {
+                    true => quote!(::edile::PinUninit<#lt, #ty>),
+                    false => quote!(::edile::Uninit<#lt, #ty>),
+                }
+            },
+        );
+        let init_def = proj_struct(
+            &input.vis,
+            None,
+            (&init_proj, &init_doc),
+            &generics,
+            fields,
+            |f| {
+                let ty = &f.ty;
+                match is_pinned(f) {
+                    true => quote!(::core::pin::Pin<::edile::Init<#lt, #ty>>),
+                    false => quote!(::edile::Init<#lt, #ty>),
+                }
+            },
+        );
+
+        let members = members(fields);
+        let ctors = fields.iter().map(|f| match is_pinned(f) {
+            true => quote!(::edile::PinUninit::from_ptr),
+            false => quote!(::edile::Uninit::from_ptr),
+        });
+        let expr = quote! {
+            #uninit_proj {
+                #( #members: #ctors(::core::ptr::addr_of_mut!((*ptr).#members)), )*
+            }
+        };
+
+        (
+            quote!(#uninit_def #init_def),
+            quote!(#uninit_proj #proj_ty_generics),
+            quote!(#init_proj #proj_ty_generics),
+            expr,
+        )
+    };
+
+    let pin_mut = derive_struct_pin_mut(input, fields);
+
+    let pinned_fields = format_ident!("__{}PinnedFields", ident);
+    let pin_lt: Lifetime = parse_quote!('__pin);
+    let pin_generics = with_lifetime(&input.generics, &pin_lt);
+    let (pin_impl_generics, pin_ty_generics, _) = pin_generics.split_for_impl();
+    let mut unpin_generics = pin_generics.clone();
+    unpin_generics
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(#pinned_fields #pin_ty_generics: ::core::marker::Unpin));
+    let unpin_where_clause = &unpin_generics.where_clause;
+
+    let not_packed = assert_not_packed(input, fields);
+
+    // A `Drop` impl would get a `&mut Self`, allowing to move out of the pinned fields,
+    // so the destructor must go through `PinnedDrop` instead.
+    let drop_impl = if pinned_drop {
+        quote! {
+            impl #impl_generics ::core::ops::Drop for #ident #ty_generics #where_clause {
+                fn drop(&mut self) {
+                    // SAFETY: The value is never used again after being dropped,
+                    // so it can't be moved after being pinned here.
+                    let this = unsafe { ::core::pin::Pin::new_unchecked(self) };
+                    // SAFETY: This is the `Drop` impl generated by the derive.
+                    let token = unsafe { ::edile::__private::pinned_drop_token() };
+                    ::edile::PinnedDrop::drop(this, token);
+                }
+            }
+        }
+    } else {
+        // Without the attribute a `PinnedDrop` impl would never be called.
+        quote! {
+            const _: () = {
+                trait PinProjConstructMustUsePinnedDropInsteadOfDrop {}
+                #[allow(drop_bounds)]
+                impl<T: ::core::ops::Drop> PinProjConstructMustUsePinnedDropInsteadOfDrop for T {}
+                impl #impl_generics PinProjConstructMustUsePinnedDropInsteadOfDrop
+                    for #ident #ty_generics #where_clause {}
+
+                trait PinnedDropRequiresPinPinnedDropAttribute {}
+                impl<T: ::edile::PinnedDrop> PinnedDropRequiresPinPinnedDropAttribute for T {}
+                impl #impl_generics PinnedDropRequiresPinPinnedDropAttribute
+                    for #ident #ty_generics #where_clause {}
+            };
+        }
+    };
+
+    Ok(quote! {
+        #proj_defs
+        #not_packed
+        #drop_impl
+        #pin_mut
+
+        impl #lt_impl_generics ::edile::project::WithPinUninitProj<#lt> for #ident #ty_generics
+        #where_clause
+        {
+            type PinUninitProj = #uninit_proj_ty;
+        }
+
+        impl #lt_impl_generics ::edile::project::WithPinInitProj<#lt> for #ident #ty_generics
+        #where_clause
+        {
+            type PinInitProj = #init_proj_ty;
+        }
+
+        impl #impl_generics ::edile::project::PinProjConstruct for #ident #ty_generics
+        #where_clause
+        {
+            #[allow(unused_variables, unused_unsafe, clippy::unused_unit)]
+            fn pin_proj_construct<__EdileInitFn>(
+                mut uninit: ::edile::PinUninit<'_, Self>,
+                f: __EdileInitFn,
+            ) -> ::core::pin::Pin<::edile::Init<'_, Self>>
+            where
+                __EdileInitFn: for<#lt> ::core::ops::FnOnce(
+                    ::edile::__private::Lt<#lt>,
+                    ::edile::project::PinUninitProj<#lt, Self>,
+                ) -> ::edile::project::PinInitProj<#lt, Self>,
+            {
+                let ptr = uninit.as_mut_ptr();
+                // SAFETY: `PinUninit`'s invariants ensure that `ptr` is a valid pointer whose
+                // memory won't be reused until the value is dropped, so the field ones are
+                // valid too. Moreover we never expose the lifetime of the `Uninit`s and
+                // `PinUninit`s created, so `from_ptr` is safe to call.
+                let uninit_proj = unsafe { #uninit_proj_expr };
+                let init_proj = f(::core::default::Default::default(), uninit_proj);
+                ::core::mem::forget(init_proj);
+                // SAFETY: The existance of `init_proj` ensures each field has been initialized.
+                unsafe { uninit.assume_init() }
+            }
+        }
+
+        // `Self` must be `Unpin` only if its structurally pinned fields are `Unpin`.
+        // This also prevents users from implementing `Unpin` manually.
+        const _: () = {
+            #[allow(dead_code)]
+            struct #pinned_fields #pin_generics #where_clause {
+                __pin: ::core::marker::PhantomData<&#pin_lt ()>,
+                // Uses every generic parameter, and it's always `Unpin`.
+                __ty: ::core::marker::PhantomData<fn() -> #ident #ty_generics>,
+                #( #pinned_names: ::core::marker::PhantomData<#pinned_tys>, )*
+            }
+
+            impl #pin_impl_generics ::core::marker::Unpin for #ident #ty_generics
+            #unpin_where_clause
+            {
+            }
+        };
+    })
+}
+
+/// Generates the implementation of `PinProjMut` for a struct.
+fn derive_struct_pin_mut(input: &DeriveInput, fields: &Fields) -> TokenStream {
+    let ident = &input.ident;
+    let pin_mut_proj = format_ident!("{}PinMutProj", ident);
+    let pin_mut_doc = format!("Projection field by field of a `Pin<&'a mut {}>`.", ident);
+    let lt = proj_lifetime(&input.generics, "a");
+    let lt_generics = with_lifetime(&input.generics, &lt);
+    let (lt_impl_generics, _, _) = lt_generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Structs without fields are trivially projected to `()`.
+    let (proj_def, pin_mut_proj_ty, pin_mut_proj_expr) = if fields.is_empty() {
+        (quote!(), quote!(()), quote!(()))
+    } else {
+        let generics = proj_generics(&input.generics, &lt, fields);
+        let (_, proj_ty_generics, _) = generics.split_for_impl();
+        let def = proj_struct(
+            &input.vis,
+            None,
+            (&pin_mut_proj, &pin_mut_doc),
+            &generics,
+            fields,
+            |f| {
+                let ty = &f.ty;
+                match is_pinned(f) {
+                    true => quote!(::core::pin::Pin<&#lt mut #ty>),
+                    false => quote!(&#lt mut #ty),
+                }
+            },
+        );
+        let members = members(fields);
+        let exprs = fields
+            .iter()
+            .zip(&members)
+            .map(|(f, member)| match is_pinned(f) {
+                true => quote!(::core::pin::Pin::new_unchecked(&mut this.#member)),
+                false => quote!(&mut this.#member),
+            });
+        let expr = quote! {
+            #pin_mut_proj { #( #members: #exprs, )* }
+        };
+        (def, quote!(#pin_mut_proj #proj_ty_generics), expr)
+    };
+
+    quote! {
+        #proj_def
+
+        impl #lt_impl_generics ::edile::project::WithPinMutProj<#lt> for #ident #ty_generics
+        #where_clause
+        {
+            type PinMutProj = #pin_mut_proj_ty;
+        }
+
+        impl #impl_generics ::edile::project::PinProjMut for #ident #ty_generics #where_clause {
+            #[allow(unused_variables, unused_unsafe, clippy::unused_unit)]
+            fn project_pin(
+                self: ::core::pin::Pin<&mut Self>,
+            ) -> ::edile::project::PinMutProj<'_, Self> {
+                // SAFETY: Only the fields marked with `#[pin]` are structurally pinned:
+                // `Self` is `Unpin` only if they are, it can't implement `Drop` nor be packed,
+                // and they are only exposed as pinned, so they are never moved.
+                unsafe {
+                    let this = ::core::pin::Pin::get_unchecked_mut(self);
+                    #pin_mut_proj_expr
+                }
+            }
+        }
+    }
+}
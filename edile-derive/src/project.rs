@@ -0,0 +1,573 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    Data, DataEnum, DeriveInput, Error, Expr, Fields, Generics, Ident, Lifetime, Member, Meta,
+    NestedMeta, Result, Visibility,
+};
+
+use crate::attrs::{has_default, FieldAttrs, TypeAttrs};
+use crate::utils::{
+    assert_not_packed, check_not_packed, filter_fields, members, proj_generics, proj_lifetime,
+    proj_struct, used_generics, variant_method, with_lifetime,
+};
+
+pub fn derive(input: DeriveInput) -> Result<TokenStream> {
+    match &input.data {
+        Data::Struct(data) => {
+            check_not_packed(&input)?;
+            let not_packed = assert_not_packed(&input, &data.fields);
+            let construct = derive_struct(&input, &data.fields)?;
+            Ok(quote!(#construct #not_packed))
+        }
+        Data::Enum(data) => derive_enum(&input, data),
+        Data::Union(data) => Err(Error::new_spanned(
+            data.union_token,
+            "unions can't be projected field by field",
+        )),
+    }
+}
+
+pub fn derive_mut(input: DeriveInput) -> Result<TokenStream> {
+    match &input.data {
+        Data::Struct(data) => {
+            check_not_packed(&input)?;
+            let not_packed = assert_not_packed(&input, &data.fields);
+            let mut_proj = derive_struct_mut(&input, &data.fields);
+            Ok(quote!(#mut_proj #not_packed))
+        }
+        Data::Enum(data) => Ok(derive_enum_mut(&input, data)),
+        Data::Union(data) => Err(Error::new_spanned(
+            data.union_token,
+            "unions can't be projected field by field",
+        )),
+    }
+}
+
+/// Generates the `Uninit` and `Init` projection structs for the given non-empty `fields`,
+/// with the given `generics` whose first parameter is the lifetime `lt`.
+///
+/// If `field_vis` is `None` the fields of the projections will have the same visibility
+/// as the original fields.
+fn proj_structs(
+    vis: &Visibility,
+    field_vis: Option<&Visibility>,
+    uninit_proj: (&Ident, &str),
+    init_proj: (&Ident, &str),
+    (generics, lt): (&Generics, &Lifetime),
+    fields: &Fields,
+) -> TokenStream {
+    let uninit_proj = proj_struct(vis, field_vis, uninit_proj, generics, fields, |f| {
+        let ty = &f.ty;
+        quote!(::edile::Uninit<#lt, #ty>)
+    });
+    let init_proj = proj_struct(vis, field_vis, init_proj, generics, fields, |f| {
+        let ty = &f.ty;
+        quote!(::edile::Init<#lt, #ty>)
+    });
+    quote!(#uninit_proj #init_proj)
+}
+
+/// Generates an expression that creates the projection `proj` of the `fields`
+/// of the struct pointed by the raw pointer `ptr`, accessing them through `ptr_members`.
+fn proj_expr(proj: &Ident, ptr: &Ident, fields: &Fields, ptr_members: &[Member]) -> TokenStream {
+    let members = members(fields);
+    quote! {
+        #proj {
+            #( #members: ::edile::Uninit::from_ptr(
+                ::core::ptr::addr_of_mut!((*#ptr).#ptr_members)
+            ), )*
+        }
+    }
+}
+
+fn derive_struct(input: &DeriveInput, fields: &Fields) -> Result<TokenStream> {
+    let ident = &input.ident;
+    let lt = proj_lifetime(&input.generics, "a");
+    let lt_generics = with_lifetime(&input.generics, &lt);
+    let (lt_impl_generics, _, _) = lt_generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let uninit_proj = format_ident!("{}UninitProj", ident);
+    let init_proj = format_ident!("{}InitProj", ident);
+    let uninit_doc = format!("Projection field by field of an `Uninit<'a, {}>`.", ident);
+    let init_doc = format!("Projection field by field of an `Init<'a, {}>`.", ident);
+
+    let TypeAttrs { post_init } = TypeAttrs::parse(&input.attrs)?;
+    let field_attrs = fields
+        .iter()
+        .map(FieldAttrs::parse)
+        .collect::<Result<Vec<_>>>()?;
+
+    // Fields with a default value are not part of the projection.
+    let (projected, projected_members) = filter_fields(fields, |f| !has_default(f));
+    let (default_members, default_exprs): (Vec<_>, Vec<_>) = members(fields)
+        .into_iter()
+        .zip(field_attrs)
+        .filter_map(|(member, attrs)| Some((member, attrs.default?)))
+        .unzip();
+
+    // Structs without projected fields are trivially projected to `()`.
+    let (proj_defs, uninit_proj_ty, init_proj_ty, uninit_proj_expr) = if projected.is_empty() {
+        (quote!(), quote!(()), quote!(()), quote!(()))
+    } else {
+        let generics = proj_generics(&input.generics, &lt, &projected);
+        let (_, proj_ty_generics, _) = generics.split_for_impl();
+        let defs = proj_structs(
+            &input.vis,
+            None,
+            (&uninit_proj, &uninit_doc),
+            (&init_proj, &init_doc),
+            (&generics, &lt),
+            &projected,
+        );
+        let expr = proj_expr(
+            &uninit_proj,
+            &format_ident!("ptr"),
+            &projected,
+            &projected_members,
+        );
+        (
+            defs,
+            quote!(#uninit_proj #proj_ty_generics),
+            quote!(#init_proj #proj_ty_generics),
+            expr,
+        )
+    };
+
+    let post_init = post_init.map(|post_init| quote!(#post_init(&mut init);));
+
+    Ok(quote! {
+        #proj_defs
+
+        impl #lt_impl_generics ::edile::project::WithUninitProj<#lt> for #ident #ty_generics
+        #where_clause
+        {
+            type UninitProj = #uninit_proj_ty;
+        }
+
+        impl #lt_impl_generics ::edile::project::WithInitProj<#lt> for #ident #ty_generics
+        #where_clause
+        {
+            type InitProj = #init_proj_ty;
+        }
+
+        impl #impl_generics ::edile::project::ProjConstruct for #ident #ty_generics #where_clause {
+            #[allow(unused_variables, unused_unsafe, clippy::unused_unit)]
+            fn try_proj_construct<__EdileInitFn, __EdileInitErr>(
+                mut uninit: ::edile::Uninit<'_, Self>,
+                f: __EdileInitFn,
+            ) -> ::core::result::Result<::edile::Init<'_, Self>, __EdileInitErr>
+            where
+                __EdileInitFn: for<#lt> ::core::ops::FnOnce(
+                    ::edile::__private::Lt<#lt>,
+                    ::edile::project::UninitProj<#lt, Self>,
+                ) -> ::core::result::Result<::edile::project::InitProj<#lt, Self>, __EdileInitErr>,
+            {
+                let ptr = uninit.as_mut_ptr();
+                // SAFETY: `Uninit`'s invariants ensure that `ptr` is a valid pointer,
+                // so the field ones are valid too. Moreover we never expose the lifetime of
+                // the `Uninit`s created, so `from_ptr` is safe to call.
+                let uninit_proj = unsafe { #uninit_proj_expr };
+                let init_proj = f(::core::default::Default::default(), uninit_proj)?;
+                // The `Init`s are kept until the end so that the fields are dropped
+                // if initializing one of the others panics.
+                let default_init = (#({
+                    // SAFETY: Same as above.
+                    let uninit = unsafe {
+                        ::edile::Uninit::from_ptr(::core::ptr::addr_of_mut!((*ptr).#default_members))
+                    };
+                    uninit.init(#default_exprs)
+                },)*);
+                ::core::mem::forget(init_proj);
+                ::core::mem::forget(default_init);
+                // SAFETY: The existance of `init_proj` and `default_init` ensures each field
+                // has been initialized.
+                let mut init = unsafe { uninit.assume_init() };
+                #post_init
+                ::core::result::Result::Ok(init)
+            }
+        }
+    })
+}
+
+/// Generates the implementation of `ProjMut` for a struct.
+fn derive_struct_mut(input: &DeriveInput, fields: &Fields) -> TokenStream {
+    let ident = &input.ident;
+    let mut_proj = format_ident!("{}MutProj", ident);
+    let mut_doc = format!("Projection field by field of a `&'a mut {}`.", ident);
+    let lt = proj_lifetime(&input.generics, "a");
+    let lt_generics = with_lifetime(&input.generics, &lt);
+    let (lt_impl_generics, _, _) = lt_generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Structs without fields are trivially projected to `()`.
+    let (proj_def, mut_proj_ty, mut_proj_expr) = if fields.is_empty() {
+        (quote!(), quote!(()), quote!(()))
+    } else {
+        let generics = proj_generics(&input.generics, &lt, fields);
+        let (_, proj_ty_generics, _) = generics.split_for_impl();
+        let def = proj_struct(
+            &input.vis,
+            None,
+            (&mut_proj, &mut_doc),
+            &generics,
+            fields,
+            |f| {
+                let ty = &f.ty;
+                quote!(&#lt mut #ty)
+            },
+        );
+        let members = members(fields);
+        let expr = quote! {
+            #mut_proj { #( #members: &mut self.#members, )* }
+        };
+        (def, quote!(#mut_proj #proj_ty_generics), expr)
+    };
+
+    quote! {
+        #proj_def
+
+        impl #lt_impl_generics ::edile::project::WithMutProj<#lt> for #ident #ty_generics
+        #where_clause
+        {
+            type MutProj = #mut_proj_ty;
+        }
+
+        impl #impl_generics ::edile::project::ProjMut for #ident #ty_generics #where_clause {
+            #[allow(clippy::unused_unit)]
+            fn project_mut(&mut self) -> ::edile::project::MutProj<'_, Self> {
+                #mut_proj_expr
+            }
+        }
+    }
+}
+
+/// Generates the implementation of `ProjMut` for an enum. The projection
+/// is an enum with the same variants.
+fn derive_enum_mut(input: &DeriveInput, data: &DataEnum) -> TokenStream {
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let mut_proj = format_ident!("{}MutProj", ident);
+    let mut_doc = format!("Projection field by field of a `&'a mut {}`.", ident);
+    let lt = proj_lifetime(&input.generics, "a");
+    let lt_generics = with_lifetime(&input.generics, &lt);
+    let (lt_impl_generics, lt_ty_generics, _) = lt_generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut variant_defs = Vec::new();
+    let mut match_arms = Vec::new();
+    for variant in &data.variants {
+        let variant_ident = &variant.ident;
+        let members = members(&variant.fields);
+        let bindings = (0..members.len())
+            .map(|idx| format_ident!("__field{}", idx))
+            .collect::<Vec<_>>();
+        let tys = variant.fields.iter().map(|f| &f.ty);
+        variant_defs.push(match &variant.fields {
+            Fields::Named(_) => quote!(#variant_ident { #( #members: &#lt mut #tys, )* }),
+            Fields::Unnamed(_) => quote!(#variant_ident( #( &#lt mut #tys, )* )),
+            Fields::Unit => quote!(#variant_ident),
+        });
+        match_arms.push(quote! {
+            #ident::#variant_ident { #( #members: #bindings, )* } =>
+                #mut_proj::#variant_ident { #( #members: #bindings, )* }
+        });
+    }
+
+    // The lifetime and the generic parameters must be used even if no variant has fields.
+    if data
+        .variants
+        .iter()
+        .all(|variant| variant.fields.is_empty())
+    {
+        variant_defs.push(quote! {
+            #[doc(hidden)]
+            __Phantom(
+                ::core::marker::PhantomData<&#lt mut #ident #ty_generics>,
+                ::core::convert::Infallible,
+            )
+        });
+    }
+
+    quote! {
+        #[doc = #mut_doc]
+        #[allow(dead_code)]
+        #vis enum #mut_proj #lt_generics #where_clause {
+            #( #variant_defs, )*
+        }
+
+        impl #lt_impl_generics ::edile::project::WithMutProj<#lt> for #ident #ty_generics
+        #where_clause
+        {
+            type MutProj = #mut_proj #lt_ty_generics;
+        }
+
+        impl #impl_generics ::edile::project::ProjMut for #ident #ty_generics #where_clause {
+            fn project_mut(&mut self) -> ::edile::project::MutProj<'_, Self> {
+                match self {
+                    #( #match_arms, )*
+                }
+            }
+        }
+    }
+}
+
+/// Returns the primitive integer type used for the discriminant of an enum,
+/// as specified by its `#[repr(...)]` attribute.
+fn enum_repr(input: &DeriveInput) -> Result<Ident> {
+    const INTS: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+
+    let mut repr = None;
+    for attr in input.attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+        if let Meta::List(list) = attr.parse_meta()? {
+            for nested in &list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("C") => {
+                        return Err(Error::new_spanned(
+                            path,
+                            "`#[repr(C)]` enums are not supported, use only a primitive representation",
+                        ));
+                    }
+                    NestedMeta::Meta(Meta::Path(path))
+                        if INTS.iter().any(|int| path.is_ident(int)) =>
+                    {
+                        repr = path.get_ident().cloned();
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    repr.ok_or_else(|| {
+        Error::new_spanned(
+            &input.ident,
+            "in-place construction of enums requires a primitive representation, e.g. `#[repr(u8)]`",
+        )
+    })
+}
+
+fn derive_enum(input: &DeriveInput, data: &DataEnum) -> Result<TokenStream> {
+    if data.variants.is_empty() {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "enums without variants can't be initialized",
+        ));
+    }
+
+    let vis = &input.vis;
+    let ident = &input.ident;
+    let repr = enum_repr(input)?;
+    let TypeAttrs { post_init } = TypeAttrs::parse(&input.attrs)?;
+    for field in data.variants.iter().flat_map(|variant| &variant.fields) {
+        if FieldAttrs::parse(field)?.default.is_some() {
+            return Err(Error::new_spanned(
+                field,
+                "fields of enum variants can't have a default value",
+            ));
+        }
+    }
+    let uninit_proj = format_ident!("{}UninitProj", ident);
+    let init_proj = format_ident!("{}InitProj", ident);
+    let uninit_doc = format!(
+        "Projection of an `Uninit<'a, {}>`. Use it to choose which variant to initialize.",
+        ident
+    );
+    let init_doc = format!("Projection of an `Init<'a, {}>`.", ident);
+
+    let lt = proj_lifetime(&input.generics, "a");
+    let lt_generics = with_lifetime(&input.generics, &lt);
+    let (lt_impl_generics, lt_ty_generics, _) = lt_generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    // The lifetime of the variants' projections in the closures passed to the methods.
+    let variant_lt = proj_lifetime(&lt_generics, "b");
+
+    let mut variant_defs = TokenStream::new();
+    let mut layout_defs = TokenStream::new();
+    let mut variant_methods = TokenStream::new();
+
+    // The discriminant of the current variant, expressed as the last explicit
+    // discriminant plus an offset.
+    let mut last_discr: Option<&Expr> = None;
+    let mut offset = 0usize;
+
+    for variant in &data.variants {
+        if let Some((_, discr)) = &variant.discriminant {
+            last_discr = Some(discr);
+            offset = 0;
+        }
+        let offset_lit = proc_macro2::Literal::usize_unsuffixed(offset);
+        let discr = match last_discr {
+            Some(discr) => quote!((#discr) + #offset_lit),
+            None => offset_lit.into_token_stream(),
+        };
+        offset += 1;
+
+        let variant_ident = &variant.ident;
+        let method = variant_method(variant_ident);
+        let layout = format_ident!("__EdileLayout{}", variant_ident);
+        let fields = &variant.fields;
+        let field_tys = fields.iter().map(|f| &f.ty);
+        let layout_members = members(fields)
+            .into_iter()
+            .map(|member| match member {
+                Member::Named(_) => member,
+                Member::Unnamed(idx) => Member::Named(format_ident!("_{}", idx)),
+            })
+            .collect::<Vec<_>>();
+
+        // A `#[repr(Int)]` enum is laid out like a `#[repr(C)]` union of `#[repr(C)]`
+        // structs, one for each variant, whose first field is the discriminant.
+        // See <https://rust-lang.github.io/rfcs/2195-really-tagged-unions.html>
+        let layout_generics = used_generics(&input.generics, fields);
+        let (_, layout_ty_generics, layout_where_clause) = layout_generics.split_for_impl();
+        layout_defs.extend(quote! {
+            #[repr(C)]
+            #[allow(dead_code)]
+            struct #layout #layout_generics #layout_where_clause {
+                __edile_tag: #repr,
+                #( #layout_members: #field_tys, )*
+            }
+        });
+        let write_tag = quote! {
+            let ptr = uninit.as_mut_ptr().cast::<#layout #layout_ty_generics>();
+            // SAFETY: `Uninit`'s invariants ensure that `ptr` is a valid pointer and
+            // the enum has the same layout as `#layout` when it is this variant.
+            unsafe { ::core::ptr::addr_of_mut!((*ptr).__edile_tag).write(#discr) };
+        };
+
+        if fields.is_empty() {
+            let doc = format!("Initializes the enum as the `{}` variant.", variant_ident);
+            variant_methods.extend(quote! {
+                #[doc = #doc]
+                pub fn #method(self) -> #init_proj #lt_ty_generics {
+                    let mut uninit = self.uninit;
+                    #write_tag
+                    // SAFETY: This variant has no fields and we just wrote its discriminant,
+                    // so the enum is fully initialized.
+                    #init_proj { init: unsafe { uninit.assume_init() } }
+                }
+            });
+            continue;
+        }
+
+        let variant_uninit_proj = format_ident!("{}{}UninitProj", ident, variant_ident);
+        let variant_init_proj = format_ident!("{}{}InitProj", ident, variant_ident);
+        let variant_uninit_doc = format!(
+            "Projection field by field of the `{}` variant of an `Uninit<'a, {}>`.",
+            variant_ident, ident
+        );
+        let variant_init_doc = format!(
+            "Projection field by field of the `{}` variant of an `Init<'a, {}>`.",
+            variant_ident, ident
+        );
+        let variant_generics = proj_generics(&input.generics, &lt, fields);
+        variant_defs.extend(proj_structs(
+            vis,
+            Some(&syn::parse_quote!(pub)),
+            (&variant_uninit_proj, &variant_uninit_doc),
+            (&variant_init_proj, &variant_init_doc),
+            (&variant_generics, &lt),
+            fields,
+        ));
+        let variant_generics = proj_generics(&input.generics, &variant_lt, fields);
+        let (_, variant_ty_generics, _) = variant_generics.split_for_impl();
+
+        let proj = proj_expr(
+            &variant_uninit_proj,
+            &format_ident!("ptr"),
+            fields,
+            &layout_members,
+        );
+        let doc = format!(
+            "Initializes the enum as the `{}` variant, using `f` to initialize its fields.",
+            variant_ident
+        );
+        variant_methods.extend(quote! {
+            #[doc = #doc]
+            pub fn #method<F>(self, f: F) -> #init_proj #lt_ty_generics
+            where
+                F: for<#variant_lt> ::core::ops::FnOnce(
+                    #variant_uninit_proj #variant_ty_generics,
+                ) -> #variant_init_proj #variant_ty_generics,
+            {
+                let mut uninit = self.uninit;
+                #write_tag
+                // SAFETY: `ptr` is valid, so the field ones are valid too. Moreover we never
+                // expose the lifetime of the `Uninit`s created, so `from_ptr` is safe to call.
+                let uninit_proj = unsafe { #proj };
+                let init_proj = f(uninit_proj);
+                ::core::mem::forget(init_proj);
+                // SAFETY: We wrote the discriminant and the existance of `init_proj` ensures
+                // each field of the variant has been initialized.
+                #init_proj { init: unsafe { uninit.assume_init() } }
+            }
+        });
+    }
+
+    let post_init = post_init.map(|post_init| quote!(#post_init(&mut init);));
+
+    Ok(quote! {
+        #[doc = #uninit_doc]
+        #vis struct #uninit_proj #lt_generics #where_clause {
+            uninit: ::edile::Uninit<#lt, #ident #ty_generics>,
+        }
+
+        #[doc = #init_doc]
+        #vis struct #init_proj #lt_generics #where_clause {
+            #[allow(dead_code)]
+            init: ::edile::Init<#lt, #ident #ty_generics>,
+        }
+
+        #variant_defs
+
+        // The layouts can't be defined inside the methods since they may need
+        // the generic parameters of the enum.
+        const _: () = {
+            #layout_defs
+
+            impl #lt_impl_generics #uninit_proj #lt_ty_generics #where_clause {
+                #variant_methods
+            }
+        };
+
+        impl #lt_impl_generics ::edile::project::WithUninitProj<#lt> for #ident #ty_generics
+        #where_clause
+        {
+            type UninitProj = #uninit_proj #lt_ty_generics;
+        }
+
+        impl #lt_impl_generics ::edile::project::WithInitProj<#lt> for #ident #ty_generics
+        #where_clause
+        {
+            type InitProj = #init_proj #lt_ty_generics;
+        }
+
+        impl #impl_generics ::edile::project::ProjConstruct for #ident #ty_generics #where_clause {
+            fn try_proj_construct<__EdileInitFn, __EdileInitErr>(
+                mut uninit: ::edile::Uninit<'_, Self>,
+                f: __EdileInitFn,
+            ) -> ::core::result::Result<::edile::Init<'_, Self>, __EdileInitErr>
+            where
+                __EdileInitFn: for<#lt> ::core::ops::FnOnce(
+                    ::edile::__private::Lt<#lt>,
+                    ::edile::project::UninitProj<#lt, Self>,
+                ) -> ::core::result::Result<::edile::project::InitProj<#lt, Self>, __EdileInitErr>,
+            {
+                // SAFETY: `Uninit`'s invariants ensure that the pointer is valid. Moreover
+                // we never expose the lifetime of the `Uninit` created, so `from_ptr`
+                // is safe to call.
+                let uninit_proj = #uninit_proj {
+                    uninit: unsafe { ::edile::Uninit::from_ptr(uninit.as_mut_ptr()) },
+                };
+                let init_proj = f(::core::default::Default::default(), uninit_proj)?;
+                ::core::mem::forget(init_proj);
+                // SAFETY: The existance of `init_proj` ensures the enum has been initialized.
+                let mut init = unsafe { uninit.assume_init() };
+                #post_init
+                ::core::result::Result::Ok(init)
+            }
+        }
+    })
+}
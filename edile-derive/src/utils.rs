@@ -0,0 +1,256 @@
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use std::collections::HashSet;
+use syn::{
+    DeriveInput, Error, Field, Fields, GenericParam, Generics, Ident, Index, Lifetime, LifetimeDef,
+    Member, Meta, NestedMeta, Result, Visibility,
+};
+
+/// Returns the `Member`s that can be used to access each of the given `fields`.
+pub fn members(fields: &Fields) -> Vec<Member> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(idx)),
+        })
+        .collect()
+}
+
+/// Returns a lifetime named after `base` that doesn't clash with the ones in `generics`.
+pub fn proj_lifetime(generics: &Generics, base: &str) -> Lifetime {
+    let mut name = String::from(base);
+    let mut idx = 0;
+    while generics.lifetimes().any(|def| def.lifetime.ident == name) {
+        name = format!("{}{}", base, idx);
+        idx += 1;
+    }
+    Lifetime::new(&format!("'{}", name), Span::call_site())
+}
+
+/// Returns `generics` with the lifetime `lt` prepended.
+pub fn with_lifetime(generics: &Generics, lt: &Lifetime) -> Generics {
+    let mut generics = generics.clone();
+    let def = LifetimeDef::new(lt.clone());
+    generics.params.insert(0, GenericParam::Lifetime(def));
+    generics
+}
+
+/// Collects the identifiers in `tokens`, including the ones of lifetimes.
+fn collect_idents(tokens: TokenStream, idents: &mut HashSet<Ident>) {
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => {
+                idents.insert(ident);
+            }
+            TokenTree::Group(group) => collect_idents(group.stream(), idents),
+            _ => {}
+        }
+    }
+}
+
+/// Returns the identifier of a generic parameter.
+fn param_ident(param: &GenericParam) -> &Ident {
+    match param {
+        GenericParam::Type(param) => &param.ident,
+        GenericParam::Lifetime(param) => &param.lifetime.ident,
+        GenericParam::Const(param) => &param.ident,
+    }
+}
+
+/// Returns `generics` without the parameters that aren't mentioned by the types of `fields`,
+/// since they can't be declared by structs containing only those fields. Bounds, defaults
+/// and predicates of the `where` clause mentioning the removed parameters are removed too.
+pub fn used_generics(generics: &Generics, fields: &Fields) -> Generics {
+    let mut used = HashSet::new();
+    fields
+        .iter()
+        .for_each(|f| collect_idents(f.ty.to_token_stream(), &mut used));
+
+    let mut generics = generics.clone();
+    let (kept, removed): (Vec<_>, Vec<_>) = generics
+        .params
+        .into_iter()
+        .partition(|param| used.contains(param_ident(param)));
+    let removed = removed.iter().map(param_ident).cloned().collect::<Vec<_>>();
+    let keep = |tokens: &dyn ToTokens| {
+        let mut idents = HashSet::new();
+        collect_idents(tokens.to_token_stream(), &mut idents);
+        removed.iter().all(|ident| !idents.contains(ident))
+    };
+
+    generics.params = kept
+        .into_iter()
+        .map(|mut param| {
+            match &mut param {
+                GenericParam::Type(param) => {
+                    param.bounds = param.bounds.iter().filter(|b| keep(b)).cloned().collect();
+                    if param.bounds.is_empty() {
+                        param.colon_token = None;
+                    }
+                    if matches!(&param.default, Some(ty) if !keep(ty)) {
+                        param.eq_token = None;
+                        param.default = None;
+                    }
+                }
+                GenericParam::Lifetime(param) => {
+                    param.bounds = param.bounds.iter().filter(|b| keep(b)).cloned().collect();
+                    if param.bounds.is_empty() {
+                        param.colon_token = None;
+                    }
+                }
+                GenericParam::Const(param) => {
+                    if matches!(&param.default, Some(expr) if !keep(expr)) {
+                        param.eq_token = None;
+                        param.default = None;
+                    }
+                }
+            }
+            param
+        })
+        .collect();
+
+    if let Some(where_clause) = &mut generics.where_clause {
+        where_clause.predicates = where_clause
+            .predicates
+            .iter()
+            .filter(|predicate| keep(predicate))
+            .cloned()
+            .collect();
+    }
+    generics
+}
+
+/// Returns the generics of a projection of `fields` with the lifetime `lt`.
+pub fn proj_generics(generics: &Generics, lt: &Lifetime, fields: &Fields) -> Generics {
+    with_lifetime(&used_generics(generics, fields), lt)
+}
+
+/// Generates a projection struct `name` with the given `generics` and the same shape as
+/// the given non-empty `fields`, where the type of each field is given by `field_ty`.
+///
+/// If `field_vis` is `None` the fields of the projection will have the same visibility
+/// as the original fields.
+///
+/// The projection is allowed to be dead code, since users of private types may not need it.
+pub fn proj_struct(
+    vis: &Visibility,
+    field_vis: Option<&Visibility>,
+    (name, doc): (&Ident, &str),
+    generics: &Generics,
+    fields: &Fields,
+    field_ty: impl Fn(&Field) -> TokenStream,
+) -> TokenStream {
+    let field_vis = fields
+        .iter()
+        .map(|f| field_vis.unwrap_or(&f.vis))
+        .collect::<Vec<_>>();
+    let field_tys = fields.iter().map(field_ty).collect::<Vec<_>>();
+    let members = members(fields);
+    let where_clause = &generics.where_clause;
+
+    match fields {
+        Fields::Named(_) => quote! {
+            #[doc = #doc]
+            #[allow(dead_code)]
+            #vis struct #name #generics #where_clause {
+                #( #field_vis #members: #field_tys, )*
+            }
+        },
+        Fields::Unnamed(_) => quote! {
+            #[doc = #doc]
+            #[allow(dead_code)]
+            #vis struct #name #generics (
+                #( #field_vis #field_tys, )*
+            ) #where_clause;
+        },
+        Fields::Unit => unreachable!(),
+    }
+}
+
+/// Converts a `CamelCase` identifier into a `snake_case` one.
+fn snake_case(ident: &Ident) -> String {
+    let ident = ident.to_string();
+    let chars = ident.chars().collect::<Vec<_>>();
+    let mut snake = String::new();
+    for (idx, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && idx != 0 {
+            let prev = chars[idx - 1];
+            let next_lower = chars.get(idx + 1).is_some_and(|c| c.is_lowercase());
+            if !prev.is_uppercase() && prev != '_' || prev.is_uppercase() && next_lower {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// Returns the name of the method of an enum's `UninitProj` that initializes the given variant.
+pub fn variant_method(variant: &Ident) -> Ident {
+    format_ident!("variant_{}", snake_case(variant))
+}
+
+/// Returns only the `fields` for which `keep` returns `true`, along with the `Member`s
+/// that can be used to access them in the original `fields`.
+pub fn filter_fields(fields: &Fields, keep: impl Fn(&Field) -> bool) -> (Fields, Vec<Member>) {
+    let mut filtered = fields.clone();
+    let mut filtered_members = Vec::new();
+    let punctuated = match &mut filtered {
+        Fields::Named(fields) => &mut fields.named,
+        Fields::Unnamed(fields) => &mut fields.unnamed,
+        Fields::Unit => return (filtered, filtered_members),
+    };
+    *punctuated = fields
+        .iter()
+        .zip(members(fields))
+        .filter(|(field, _)| keep(field))
+        .map(|(field, member)| {
+            filtered_members.push(member);
+            field.clone()
+        })
+        .collect();
+    (filtered, filtered_members)
+}
+
+/// Returns an error if the struct is `#[repr(packed)]`, since its fields may be unaligned
+/// and `Uninit`s, `Own`s and references to them can't be created.
+pub fn check_not_packed(input: &DeriveInput) -> Result<()> {
+    for attr in input.attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+        if let Meta::List(list) = attr.parse_meta()? {
+            for nested in &list.nested {
+                let path = match nested {
+                    NestedMeta::Meta(Meta::Path(path)) => path,
+                    NestedMeta::Meta(Meta::List(list)) => &list.path,
+                    _ => continue,
+                };
+                if path.is_ident("packed") {
+                    return Err(Error::new_spanned(
+                        nested,
+                        "`#[repr(packed)]` structs can't be projected because their fields \
+                        may be unaligned",
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Generates a function that takes a reference to each of the `fields` of the struct,
+/// which fails to compile if the struct is `#[repr(packed)]`. This catches the attributes
+/// that [`check_not_packed`] can't see, e.g. the ones added by other macros.
+pub fn assert_not_packed(input: &DeriveInput, fields: &Fields) -> TokenStream {
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let members = members(fields);
+    quote! {
+        const _: () = {
+            #[allow(dead_code, clippy::no_effect_underscore_binding)]
+            fn __assert_not_packed #impl_generics (this: &#ident #ty_generics) #where_clause {
+                #( let _ = &this.#members; )*
+            }
+        };
+    }
+}
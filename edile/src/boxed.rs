@@ -0,0 +1,189 @@
+use crate::{AllocError, Global, Init, PinUninit, RawAlloc, Uninit};
+
+use alloc::boxed::Box;
+use core::alloc::Layout;
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::pin::Pin;
+use core::ptr;
+
+#[cfg(feature = "nightly")]
+use core::alloc::Allocator;
+
+/// Extension trait for constructing a [`Box`] in place.
+pub trait BoxExt<T>: Sized {
+    /// Allocates some memory on the heap and initializes it with the given
+    /// constructor `f`, without the value ever being on the stack.
+    ///
+    /// If `f` panics the memory is deallocated.
+    fn new_with<F>(f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>;
+
+    /// Like [`new_with`], but returns an error if the allocation fails, in which case
+    /// `f` is not called.
+    ///
+    /// [`new_with`]: BoxExt::new_with
+    fn try_new_with<F>(f: F) -> Result<Self, AllocError>
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>;
+
+    /// Allocates some memory on the heap and initializes it with the given pinned
+    /// constructor `f`, which can thus rely on the final address of the value.
+    ///
+    /// If `f` panics the memory is leaked, since the fields it initialized may have been
+    /// pinned and forgotten, and their memory must not be reused without dropping them.
+    ///
+    /// See also [`pin_proj_fn`] for constructors other than plain closures.
+    ///
+    /// [`pin_proj_fn`]: crate::pin_proj_fn
+    fn pin_with<F>(f: F) -> Pin<Self>
+    where
+        F: FnOnce(PinUninit<'_, T>) -> Pin<Init<'_, T>>;
+}
+
+impl<T> BoxExt<T> for Box<T> {
+    fn new_with<F>(f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+    {
+        init_boxed(Box::new_uninit(), f)
+    }
+
+    fn try_new_with<F>(f: F) -> Result<Self, AllocError>
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+    {
+        let ptr = Global.allocate(Layout::new::<T>())?;
+        // SAFETY: `ptr` was allocated by the global allocator with the layout of `T`, which
+        // is the same as `MaybeUninit<T>`, or it's dangling and aligned if `T` is zero sized,
+        // so the `Box` can own it.
+        let boxed = unsafe { Box::from_raw(ptr.cast::<MaybeUninit<T>>().as_ptr()) };
+        Ok(init_boxed(boxed, f))
+    }
+
+    fn pin_with<F>(f: F) -> Pin<Self>
+    where
+        F: FnOnce(PinUninit<'_, T>) -> Pin<Init<'_, T>>,
+    {
+        // If `f` panics the memory is leaked, since it may contain pinned fields that
+        // were never dropped.
+        let mut boxed = ManuallyDrop::new(Box::<T>::new_uninit());
+        // SAFETY: The pointer comes from a `Box`, so it's valid and aligned. Moreover we
+        // never expose the lifetime of the `Uninit` created, so `from_ptr` is safe to call.
+        let uninit = unsafe { Uninit::from_ptr(boxed.as_mut_ptr()) };
+        // SAFETY: The memory will be pinned, i.e. not reused until the value is dropped,
+        // once initialized.
+        let init = unsafe { uninit.pin_init_with(f) };
+        mem::forget(init);
+        // SAFETY: The existance of `init` ensures the value has been initialized.
+        let boxed = unsafe { ManuallyDrop::into_inner(boxed).assume_init() };
+        Box::into_pin(boxed)
+    }
+}
+
+/// Extension trait for constructing a [`Box`] of a slice in place.
+pub trait BoxSliceExt<T>: Sized {
+    /// Allocates a slice of `len` elements on the heap and initializes it with
+    /// the given constructor `f`, for example [`slice_each`].
+    ///
+    /// If `f` panics the memory is deallocated.
+    ///
+    /// [`slice_each`]: crate::slice_each
+    fn new_slice_with<F>(len: usize, f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, [T]>) -> Init<'_, [T]>;
+
+    /// Like [`new_slice_with`], but returns an error if the size of the slice overflows
+    /// or the allocation fails, in which case `f` is not called.
+    ///
+    /// [`new_slice_with`]: BoxSliceExt::new_slice_with
+    fn try_new_slice_with<F>(len: usize, f: F) -> Result<Self, AllocError>
+    where
+        F: FnOnce(Uninit<'_, [T]>) -> Init<'_, [T]>;
+}
+
+impl<T> BoxSliceExt<T> for Box<[T]> {
+    fn new_slice_with<F>(len: usize, f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, [T]>) -> Init<'_, [T]>,
+    {
+        init_boxed_slice(Box::new_uninit_slice(len), f)
+    }
+
+    fn try_new_slice_with<F>(len: usize, f: F) -> Result<Self, AllocError>
+    where
+        F: FnOnce(Uninit<'_, [T]>) -> Init<'_, [T]>,
+    {
+        let layout = Layout::array::<T>(len).map_err(|_| AllocError)?;
+        let ptr = Global.allocate(layout)?.cast::<MaybeUninit<T>>();
+        let ptr = ptr::slice_from_raw_parts_mut(ptr.as_ptr(), len);
+        // SAFETY: `ptr` was allocated by the global allocator with the layout of `[T]` with
+        // `len` elements, or it's dangling and aligned if the slice is zero sized, so the
+        // `Box` can own it.
+        let boxed = unsafe { Box::from_raw(ptr) };
+        Ok(init_boxed_slice(boxed, f))
+    }
+}
+
+/// Initializes the value of `boxed` with the constructor `f`.
+fn init_boxed<T, F>(mut boxed: Box<MaybeUninit<T>>, f: F) -> Box<T>
+where
+    F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+{
+    // SAFETY: The pointer returned by `boxed` is valid and aligned. Moreover
+    // we never expose the lifetime of the `Uninit` created, so `from_ptr` is
+    // safe to call.
+    let uninit = unsafe { Uninit::from_ptr(boxed.as_mut_ptr()) };
+    let init = f(uninit);
+    mem::forget(init);
+    // SAFETY: The existance of `init` ensures the value has been initialized.
+    unsafe { boxed.assume_init() }
+}
+
+/// Initializes the elements of `boxed` with the constructor `f`.
+fn init_boxed_slice<T, F>(mut boxed: Box<[MaybeUninit<T>]>, f: F) -> Box<[T]>
+where
+    F: FnOnce(Uninit<'_, [T]>) -> Init<'_, [T]>,
+{
+    // Can't use `cast` because it requires `U` to be `Sized`.
+    let ptr = &mut *boxed as *mut [MaybeUninit<T>] as *mut [T];
+    // SAFETY: `ptr` comes from a mutable reference, so it's valid and aligned.
+    // Moreover we never expose the lifetime of the `Uninit` created, so `from_ptr`
+    // is safe to call.
+    let uninit = unsafe { Uninit::from_ptr(ptr) };
+    let init = f(uninit);
+    mem::forget(init);
+    // SAFETY: The existance of `init` ensures the elements have been initialized.
+    unsafe { boxed.assume_init() }
+}
+
+/// Extension trait for constructing a [`Box`] with a custom allocator in place.
+#[cfg(feature = "nightly")]
+pub trait BoxAllocExt<T, A: Allocator> {
+    /// Allocates some memory with `alloc` and initializes it with the given
+    /// constructor `f`, without the value ever being on the stack.
+    ///
+    /// If `f` panics the memory is deallocated.
+    fn new_in_with<F>(alloc: A, f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>;
+}
+
+#[cfg(feature = "nightly")]
+impl<T, A: Allocator> BoxAllocExt<T, A> for Box<T, A> {
+    fn new_in_with<F>(alloc: A, f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+    {
+        let mut boxed = Box::<T, A>::new_uninit_in(alloc);
+        let ptr = boxed.as_mut_ptr();
+        // SAFETY: `ptr` comes from a `Box`, so it's valid and aligned.
+        // Moreover we never expose the lifetime of the `Uninit` created, so `from_ptr`
+        // is safe to call.
+        let uninit = unsafe { Uninit::from_ptr(ptr) };
+        let init = f(uninit);
+        mem::forget(init);
+        // SAFETY: The existance of `init` ensures the value has been initialized.
+        unsafe { boxed.assume_init() }
+    }
+}
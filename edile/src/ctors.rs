@@ -1,8 +1,12 @@
 use crate::__private::Lt;
-use crate::project::{InitProj, ProjConstruct, UninitProj};
-use crate::{Init, Own, Uninit};
+use crate::project::{
+    InitProj, PinInitProj, PinProjConstruct, PinUninitProj, ProjConstruct, UninitProj,
+};
+use crate::{Init, Own, PinUninit, Uninit};
 
+use core::convert::Infallible;
 use core::mem;
+use core::pin::Pin;
 use core::ptr;
 
 /// Creates a constructor that will initialize an [`Uninit<T>`] by
@@ -21,7 +25,7 @@ pub fn move_from<T>(value: Own<'_, T>) -> impl FnOnce(Uninit<'_, T>) -> Init<'_,
 /// Creates a constructor that will initialize an [`Uninit<T>`] by
 /// calling the provided closure with its projection.
 ///
-/// See also [`ProjConstruct`] and (TODO: mention derive macro)
+/// See also [`ProjConstruct`] and its derive macro, available with the `derive` feature.
 pub fn proj_fn<T, F>(f: F) -> impl FnOnce(Uninit<'_, T>) -> Init<'_, T>
 where
     T: ProjConstruct + ?Sized,
@@ -30,6 +34,33 @@ where
     move |uninit| T::proj_construct(uninit, f)
 }
 
+/// Creates a fallible constructor that will initialize an [`Uninit<T>`] by
+/// calling the provided closure with its projection.
+///
+/// If the closure returns an error the fields it already initialized are dropped
+/// when their [`Init`]s go out of scope, so `?` can be used to propagate errors.
+///
+/// See also [`ProjConstruct::try_proj_construct`].
+pub fn try_proj_fn<T, F, E>(f: F) -> impl FnOnce(Uninit<'_, T>) -> Result<Init<'_, T>, E>
+where
+    T: ProjConstruct + ?Sized,
+    F: for<'a> FnOnce(Lt<'a>, UninitProj<'a, T>) -> Result<InitProj<'a, T>, E>,
+{
+    move |uninit| T::try_proj_construct(uninit, f)
+}
+
+/// Creates a constructor that will initialize a [`PinUninit<T>`] by
+/// calling the provided closure with its projection.
+///
+/// See also [`PinProjConstruct`] and its derive macro, available with the `derive` feature.
+pub fn pin_proj_fn<T, F>(f: F) -> impl FnOnce(PinUninit<'_, T>) -> Pin<Init<'_, T>>
+where
+    T: PinProjConstruct + ?Sized,
+    F: for<'a> FnOnce(Lt<'a>, PinUninitProj<'a, T>) -> PinInitProj<'a, T>,
+{
+    move |uninit| T::pin_proj_construct(uninit, f)
+}
+
 /// Creates a constructor that will initialize an `Uninit<[T; N]>` by calling
 /// the provided closure for each element, passing its index and the corresponding
 /// [`Uninit<T>`].
@@ -39,27 +70,41 @@ pub fn array_each<T, F, const N: usize>(
 where
     F: FnMut(usize, Uninit<'_, T>) -> Init<'_, T>,
 {
-    proj_fn::<[T; N], _>(move |_, proj| {
-        let mut idx = 0;
-        proj.map(|uninit| {
-            let init = f(idx, uninit);
-            idx += 1;
-            init
-        })
-    })
-    // Alternative `unsafe` way, in case the other one breaks for no reason:
-    // move |mut uninit| {
-    //     // Can't use `cast` because it requires `U` to be `Sized`.
-    //     let ptr = uninit.as_mut_ptr() as *mut [T];
-    //     // SAFETY: `ptr` is valid because returned by `uninit.as_mut_ptr()`
-    //     // and we never expose the lifetime of the `Uninit`.
-    //     let uninit_slice = unsafe { Uninit::from_ptr(ptr) };
-    //     let init = uninit_slice.init_with(slice_each(f));
-    //     mem::forget(init);
-    //     // SAFETY: The existance of `init` ensures the data pointed by `uninit`
-    //     // has been initialized.
-    //     unsafe { uninit.assume_init() }
-    // }
+    move |uninit| {
+        let res = uninit.try_init_with(try_array_each(|idx, uninit| {
+            Ok::<_, Infallible>(f(idx, uninit))
+        }));
+        unwrap_infallible(res)
+    }
+}
+
+/// Creates a fallible constructor that will initialize an `Uninit<[T; N]>` by calling
+/// the provided closure for each element, passing its index and the corresponding
+/// [`Uninit<T>`].
+///
+/// If the closure returns an error the elements already initialized are dropped and
+/// the index of the element that failed is returned along with the error.
+#[allow(clippy::type_complexity)]
+pub fn try_array_each<T, F, E, const N: usize>(
+    f: F,
+) -> impl FnOnce(Uninit<'_, [T; N]>) -> Result<Init<'_, [T; N]>, (usize, E)>
+where
+    F: FnMut(usize, Uninit<'_, T>) -> Result<Init<'_, T>, E>,
+{
+    // Going through `try_slice_each` avoids creating arrays of `Uninit`s and `Init`s
+    // on the stack, which may overflow it for big arrays.
+    move |mut uninit| {
+        // Can't use `cast` because it requires `U` to be `Sized`.
+        let ptr = uninit.as_mut_ptr() as *mut [T];
+        // SAFETY: `ptr` is valid because returned by `uninit.as_mut_ptr()`
+        // and we never expose the lifetime of the `Uninit`.
+        let uninit_slice = unsafe { Uninit::from_ptr(ptr) };
+        let init = uninit_slice.try_init_with(try_slice_each(f))?;
+        mem::forget(init);
+        // SAFETY: The existance of `init` ensures the data pointed by `uninit`
+        // has been initialized.
+        Ok(unsafe { uninit.assume_init() })
+    }
 }
 
 /// Creates a constructor that will initialize an `Uninit<[T]>` by calling
@@ -68,20 +113,41 @@ where
 pub fn slice_each<T, F>(mut f: F) -> impl FnOnce(Uninit<'_, [T]>) -> Init<'_, [T]>
 where
     F: FnMut(usize, Uninit<'_, T>) -> Init<'_, T>,
+{
+    move |uninit| {
+        let res = uninit.try_init_with(try_slice_each(|idx, uninit| {
+            Ok::<_, Infallible>(f(idx, uninit))
+        }));
+        unwrap_infallible(res)
+    }
+}
+
+/// Creates a fallible constructor that will initialize an `Uninit<[T]>` by calling
+/// the provided closure for each element, passing its index and the corresponding
+/// [`Uninit<T>`].
+///
+/// If the closure returns an error the elements already initialized are dropped and
+/// the index of the element that failed is returned along with the error.
+#[allow(clippy::type_complexity)]
+pub fn try_slice_each<T, F, E>(
+    mut f: F,
+) -> impl FnOnce(Uninit<'_, [T]>) -> Result<Init<'_, [T]>, (usize, E)>
+where
+    F: FnMut(usize, Uninit<'_, T>) -> Result<Init<'_, T>, E>,
 {
     move |mut uninit| {
         // Invariant: `data` points to a slice of `current` initialized `T`s which
-        // `DropOnPanic` logically owns.
-        struct DropOnPanic<T> {
+        // `DropOnFailure` logically owns.
+        struct DropOnFailure<T> {
             data: *mut T,
             current: usize,
         }
 
-        impl<T> Drop for DropOnPanic<T> {
+        impl<T> Drop for DropOnFailure<T> {
             fn drop(&mut self) {
                 let slice = ptr::slice_from_raw_parts_mut(self.data, self.current);
-                // SAFETY: for `DropOnPanic`'s invariant `slice` is a valid slice and the elements
-                // are initialized and owned by `self`.
+                // SAFETY: for `DropOnFailure`'s invariant `slice` is a valid slice and the
+                // elements are initialized and owned by `self`.
                 unsafe { ptr::drop_in_place(slice) };
             }
         }
@@ -89,7 +155,7 @@ where
         let len = uninit.len();
         // SAFETY: `uninit.as_mut_ptr()` is a valid pointer, and currently points to 0
         // initialized elements.
-        let mut guard = DropOnPanic {
+        let mut guard = DropOnFailure {
             data: uninit.as_mut_ptr().cast::<T>(),
             current: 0,
         };
@@ -102,7 +168,8 @@ where
             // SAFETY: We just shown that `curr_ptr` is a valid `ptr`
             // and we never expose the lifetime of the `Uninit`.
             let uninit = unsafe { Uninit::from_ptr(curr_ptr) };
-            let init = f(guard.current, uninit);
+            // On error `guard` drops the elements initialized so far.
+            let init = f(guard.current, uninit).map_err(|err| (guard.current, err))?;
             mem::forget(init);
             // The existance of `init` guarantees the current element has been initialized.
             guard.current += 1;
@@ -111,8 +178,16 @@ where
         // Now that all the elements are initialized don't drop them.
         mem::forget(guard);
 
-        // SAFETY: The invariant of `DropOnPanic` and `guard.current != len` being false
+        // SAFETY: The invariant of `DropOnFailure` and `guard.current != len` being false
         // ensures the data pointed by `uninit` has been initialized.
-        unsafe { uninit.assume_init() }
+        Ok(unsafe { uninit.assume_init() })
+    }
+}
+
+/// Unwraps the result of a fallible constructor that can't fail.
+fn unwrap_infallible<T>(res: Result<T, (usize, Infallible)>) -> T {
+    match res {
+        Ok(value) => value,
+        Err((_, never)) => match never {},
     }
 }
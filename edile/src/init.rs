@@ -66,13 +66,13 @@ impl<'a, T: ?Sized> Drop for Init<'a, T> {
 
 impl<'a, T: ?Sized> AsRef<T> for Init<'a, T> {
     fn as_ref(&self) -> &T {
-        &**self
+        self
     }
 }
 
 impl<'a, T: ?Sized> AsMut<T> for Init<'a, T> {
     fn as_mut(&mut self) -> &mut T {
-        &mut **self
+        self
     }
 }
 
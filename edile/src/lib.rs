@@ -1,33 +1,51 @@
 #![no_std]
+#![cfg_attr(feature = "nightly", feature(allocator_api))]
+// Newer toolchains moved part of `allocator_api` to `allocator_ext`.
+#![cfg_attr(feature = "nightly", allow(stable_features))]
 #![deny(unsafe_op_in_unsafe_fn)]
 
-// TODO: Pinned initialization
-// TODO: Alloc extensions
-// TODO: Maybe fallible initialization?
 // TODO: Doc examples (after derive)
 // TODO: Decide policy between elided lifetimes vs '_ vs for<'a>
 
-// TODO: re-export depending on features + std case
-#[cfg(all(not(feature = "std"), feature = "alloc"))]
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
 pub mod project;
 
+#[cfg(feature = "alloc")]
+mod boxed;
 mod ctors;
 mod init;
 mod local;
 #[macro_use]
 mod macros;
 mod own;
+mod pin;
+mod raw_alloc;
+#[cfg(feature = "alloc")]
+mod rc;
 mod storage;
+#[cfg(feature = "alloc")]
+mod sync;
 mod uninit;
+#[cfg(feature = "alloc")]
+mod vec;
 
+#[cfg(feature = "alloc")]
+pub use boxed::*;
 pub use ctors::*;
 pub use init::*;
-pub use local::*;
 pub use own::*;
+pub use pin::*;
+pub use raw_alloc::*;
+#[cfg(feature = "alloc")]
+pub use rc::*;
 pub use storage::*;
+#[cfg(feature = "alloc")]
+pub use sync::*;
 pub use uninit::*;
+#[cfg(feature = "alloc")]
+pub use vec::*;
 
 #[cfg(feature = "derive")]
 pub use edile_derive::*;
@@ -36,6 +54,18 @@ pub use edile_derive::*;
 pub mod __private {
     pub use core::ops::FnOnce;
     pub use core::pin::Pin;
+    pub use core::result::Result;
+
+    /// Creates the token passed to [`PinnedDrop::drop`].
+    ///
+    /// # Safety
+    /// This must only be called by the `Drop` implementation generated by the
+    /// `PinProjConstruct` derive.
+    ///
+    /// [`PinnedDrop::drop`]: crate::PinnedDrop::drop
+    pub unsafe fn pinned_drop_token() -> crate::PinnedDropToken {
+        crate::PinnedDropToken::new()
+    }
 
     /// Private utility struct needed to workaround a limitation of the
     /// compiler where it can't determine whether some lifetime will be costrained
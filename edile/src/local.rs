@@ -1,74 +1,54 @@
-// TODO: Decide how to initialize local `Own`s
+/// Declares local [`Own`]s whose values are stored on the stack, each one in its
+/// own hidden storage that lives until the end of the enclosing block.
+///
+/// Each binding has the form `name = init` or `name: Type = init`, optionally
+/// preceded by `mut`, where `init` is one of:
+/// - `new(expr)`, which moves the value of `expr` into the storage, see [`Own::new`];
+/// - `with uninit => expr`, which initializes the storage with a closure taking
+///   the [`Uninit`] `uninit` and returning `expr`, see [`Own::new_with`];
+/// - `with_proj proj => expr`, which does the same with a closure taking the
+///   projection `proj` of the [`Uninit`], see [`proj_fn`].
+///
+/// Multiple bindings can be declared in the same invocation by separating them with `;`.
+///
+/// [`Own`]: crate::Own
+/// [`Own::new`]: crate::Own::new
+/// [`Own::new_with`]: crate::Own::new_with
+/// [`Uninit`]: crate::Uninit
+/// [`proj_fn`]: crate::proj_fn
+#[macro_export]
+macro_rules! local {
+    () => {};
+    (mut $($rest:tt)*) => {
+        $crate::__internal_helper!(@LOCAL [mut] $($rest)*);
+    };
+    ($($rest:tt)*) => {
+        $crate::__internal_helper!(@LOCAL [] $($rest)*);
+    };
+}
 
-// #[macro_export]
-// macro_rules! local {
-//     (mut $val:ident $(: $ty:ty)?) => {
-//         $crate::__helper(@LOCAL [mut $val] [$($ty)?] new_default)
-//     };
-//     ($val:ident $(: $ty:ty)?) => {
-//         $crate::__helper(@LOCAL [$val] [$($ty)?] new_default)
-//     };
-//     (mut $val:ident $(: $ty:ty)? = new ( $($args:tt)* )) => {
-//         $crate::__helper(@LOCAL [mut $val] [$($ty)?] new ($($args)*));
-//     };
-//     ($val:ident $(: $ty:ty)? = new ( $($args:tt)* )) => {
-//         $crate::__helper(@LOCAL [$val] [$($ty)?] new ($($args)*));
-//     };
-//     (mut $val:ident $(: $ty:ty)? = with $uninit:pat => $($body:tt)*) => {
-//         $crate::__helper(@LOCAL [mut $val] [$($ty)?] new_with |$uninit| $($body)*);
-//     };
-//     ($val:ident $(: $ty:ty)? = with $uninit:pat => $($body:tt)*) => {
-//         $crate::__helper(@LOCAL [$val] [$($ty)?] new_with |$uninit| $($body)*);
-//     };
-//     (mut $val:ident $(: $ty:ty)? = with_proj $proj:pat => $($body:tt)*) => {
-//         $crate::__helper(@LOCAL [mut $val] [$($ty)?] new_with_proj |_, $proj| $($body)*);
-//     };
-//     ($val:ident $(: $ty:ty)? = with_proj $proj:pat => $($body:tt)*) => {
-//         $crate::__helper(@LOCAL [$val] [$($ty)?] new_with_proj |_, $proj| $($body)*);
-//     };
-// }
-
-// #[macro_export]
-// macro_rules! local {
-//     ($(mut $($mut:tt)?)? $val:ident $(: $ty:ty)?) => {
-//         $crate::__helper(@LOCAL [$(mut $($mut)?)? $val] [$($ty)?] new_default);
-//     };
-//     ($(mut $($mut:tt)?)? $val:ident $(: $ty:ty)? = new ( $($args:tt)* )) => {
-//         $crate::__helper(@LOCAL [$(mut $($mut)?)? $val] [$($ty)?] new ($($args)*));
-//     };
-//     ($(mut $($mut:tt)?)?  $val:ident $(: $ty:ty)? = with $uninit:pat => $($body:tt)*) => {
-//         $crate::__helper(@LOCAL [$(mut $($mut)?)? $val] [$($ty)?] new_with |$uninit| $($body)*);
-//     };
-//     ($(mut $($mut:tt)?)?  $val:ident $(: $ty:ty)? = with_proj $proj:pat => $($body:tt)*) => {
-//         $crate::__helper(@LOCAL [$(mut $($mut)?)? $val] [$($ty)?] new_with_proj |_, $proj| $($body)*);
-//     };
-// }
-
-// #[macro_export]
-// macro_rules! local {
-//     ($(mut $($mut:tt)?)? $val:ident $(: $ty:ty)? $(; $($($rest:tt)+)?)?) => {
-//         $crate::__internal_helper(@LOCAL [$(mut $($mut)?)? $val] [$($ty)?] new_default);
-//         $($crate::local!($($($rest)+)?))?;
-//     };
-//     ($(mut $($mut:tt)?)? $val:ident $(: $ty:ty)? = new ( $($args:tt)* ) $(; $($($rest:tt)+)?)?) => {
-//         $crate::__internal_helper(@LOCAL [$(mut $($mut)?)? $val] [$($ty)?] new ($($args)*));
-//         $($crate::local!($($($rest)+)?))?;
-//     };
-//     ($(mut $($mut:tt)?)?  $val:ident $(: $ty:ty)? = with_proj $proj:pat => $body:expr $(; $($($rest:tt)+)?)?) => {
-//         $crate::__internal_helper(@LOCAL [$(mut $($mut)?)? $val] [$($ty)?] new_with_proj |_, $proj| $body);
-//         $($crate::local!($($($rest)+)?))?;
-//     };
-//     ($(mut $($mut:tt)?)?  $val:ident $(: $ty:ty)? = with $uninit:pat => $body:expr $(; $($($rest:tt)+)?)?) => {
-//         $crate::__internal_helper(@LOCAL [$(mut $($mut)?)? $val] [$($ty)?] new_with |$uninit| $body);
-//         $($crate::local!($($($rest)+)?))?;
-//     };
-// }
-
-// #[doc(hidden)]
-// #[macro_export]
-// macro_rules! __internal_helper {
-//     (@LOCAL [$local:pat] [$($ty:ty)?] $method:ident $($args:tt)*) => {
-//         let mut storage = ::core::mem::MaybeUninit::uninit();
-//         let $local:pat $(: Own<$ty>)? = $crate::Own::$method(&mut storage, $($args)*);
-//     };
-// }
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __internal_helper {
+    (@LOCAL [$($mut:tt)?] $val:ident $(: $ty:ty)? = new ( $value:expr ) $(; $($rest:tt)*)?) => {
+        let mut storage = ::core::mem::MaybeUninit $(::<$ty>)? ::uninit();
+        let $($mut)? $val $(: $crate::Own<'_, $ty>)? = $crate::Own::new(&mut storage, $value);
+        $($crate::local!($($rest)*);)?
+    };
+    (@LOCAL [$($mut:tt)?] $val:ident $(: $ty:ty)? = with $uninit:pat => $body:expr $(; $($rest:tt)*)?) => {
+        let mut storage = ::core::mem::MaybeUninit $(::<$ty>)? ::uninit();
+        let $($mut)? $val $(: $crate::Own<'_, $ty>)? = $crate::Own::new_with(
+            &mut storage,
+            |$uninit $(: $crate::Uninit<'_, $ty>)?| $body,
+        );
+        $($crate::local!($($rest)*);)?
+    };
+    (@LOCAL [$($mut:tt)?] $val:ident $(: $ty:ty)? = with_proj $proj:pat => $body:expr $(; $($rest:tt)*)?) => {
+        let mut storage = ::core::mem::MaybeUninit $(::<$ty>)? ::uninit();
+        let $($mut)? $val $(: $crate::Own<'_, $ty>)? = $crate::Own::new_with(
+            &mut storage,
+            $crate::proj_fn $(::<$ty, _>)? (|_, $proj| $body),
+        );
+        $($crate::local!($($rest)*);)?
+    };
+}
@@ -2,6 +2,30 @@
 #[macro_export]
 macro_rules! ctor {
     ($ty:ty) => {
-        impl $crate::__private::FnOnce($crate::Uninit<'_, $ty>) -> $crate::Init<'_, $ty>
+        impl for<'__ctor> $crate::__private::FnOnce(
+            $crate::Uninit<'__ctor, $ty>,
+        ) -> $crate::Init<'__ctor, $ty>
+    }
+}
+
+/// Utility macro to avoid repeating `impl FnOnce(Uninit<$ty>) -> Result<Init<$ty>, $err>`,
+/// the type of a fallible constructor.
+#[macro_export]
+macro_rules! try_ctor {
+    ($ty:ty, $err:ty) => {
+        impl for<'__ctor> $crate::__private::FnOnce(
+            $crate::Uninit<'__ctor, $ty>,
+        ) -> $crate::__private::Result<$crate::Init<'__ctor, $ty>, $err>
+    }
+}
+
+/// Utility macro to avoid repeating `impl FnOnce(PinUninit<$ty>) -> Pin<Init<$ty>>`,
+/// the type of a constructor for a value that will be pinned.
+#[macro_export]
+macro_rules! pin_ctor {
+    ($ty:ty) => {
+        impl for<'__ctor> $crate::__private::FnOnce(
+            $crate::PinUninit<'__ctor, $ty>,
+        ) -> $crate::__private::Pin<$crate::Init<'__ctor, $ty>>
     }
 }
@@ -1,4 +1,5 @@
-use crate::{Init, Storage, Uninit};
+use crate::project::{OwnProj, ProjMove};
+use crate::{Init, PinUninit, Storage, Uninit};
 
 use core::borrow::{Borrow, BorrowMut};
 use core::mem::{self, ManuallyDrop};
@@ -32,9 +33,18 @@ impl<'storage, T: ?Sized> Own<'storage, T> {
     }
 
     /// Converts an `Own<'storage, T>` into a `Pin<Own<'storage, T>>`.
-    pub fn into_pin(this: Self) -> Pin<Self> {
+    ///
+    /// # Safety
+    /// The returned `Pin<Own<'storage, T>>` must be dropped before the memory of the storage
+    /// is reused or invalidated, i.e. it must not be leaked. Otherwise the value could be
+    /// overwritten without being dropped, breaking the guarantees of [`Pin`].
+    /// This is the same requirement as [`pin_new_with`].
+    ///
+    /// [`pin_new_with`]: Own::pin_new_with
+    pub unsafe fn into_pin(this: Self) -> Pin<Self> {
         // SAFETY: It's not possible to move or replace the insides of a `Pin<Own<T>>`
-        // when `T: !Unpin`, so it's safe to pin it without other requirements.
+        // when `T: !Unpin`, and the caller ensures the memory won't be reused before
+        // the value is dropped.
         unsafe { Pin::new_unchecked(this) }
     }
 
@@ -47,7 +57,7 @@ impl<'storage, T: ?Sized> Own<'storage, T> {
     /// [`proj_fn`]: crate::proj_fn
     pub fn new_with<S, F>(storage: &'storage mut S, f: F) -> Self
     where
-        S: Storage<T>,
+        S: Storage<T> + ?Sized,
         F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
     {
         let ptr = storage.as_mut_ptr();
@@ -60,9 +70,67 @@ impl<'storage, T: ?Sized> Own<'storage, T> {
         // SAFETY: The existance of `init` ensures each field has been initialized.
         unsafe { Self::from_raw(ptr) }
     }
+
+    /// Like [`new_with`], but with a fallible constructor `f`.
+    ///
+    /// If `f` returns an error the error is returned and `storage` is left
+    /// uninitialized, so it can be reused.
+    ///
+    /// [`new_with`]: Own::new_with
+    pub fn try_new_with<S, F, E>(storage: &'storage mut S, f: F) -> Result<Self, E>
+    where
+        S: Storage<T> + ?Sized,
+        F: FnOnce(Uninit<'_, T>) -> Result<Init<'_, T>, E>,
+    {
+        let ptr = storage.as_mut_ptr();
+        // SAFETY: Same as `new_with`.
+        let uninit = unsafe { Uninit::from_ptr(ptr) };
+        let init = f(uninit)?;
+        mem::forget(init);
+        // SAFETY: The existance of `init` ensures each field has been initialized.
+        Ok(unsafe { Self::from_raw(ptr) })
+    }
+
+    /// Like [`new_with`], but the value is pinned, so `f` can rely on it staying
+    /// at its address.
+    ///
+    /// # Safety
+    /// The returned `Pin<Own<'storage, T>>` must be dropped before the memory of `storage`
+    /// is reused or invalidated, i.e. it must not be leaked. This is the same requirement
+    /// as [`into_pin`], since `storage` is usually a `MaybeUninit` on the stack that can be
+    /// reused once the `Own` is forgotten.
+    ///
+    /// [`new_with`]: Own::new_with
+    /// [`into_pin`]: Own::into_pin
+    pub unsafe fn pin_new_with<S, F>(storage: &'storage mut S, f: F) -> Pin<Self>
+    where
+        S: Storage<T> + ?Sized,
+        F: FnOnce(PinUninit<'_, T>) -> Pin<Init<'_, T>>,
+    {
+        let ptr = storage.as_mut_ptr();
+        // SAFETY: Same as `new_with`. Moreover the caller ensures the memory won't be
+        // reused or invalidated before the value is dropped.
+        let uninit = unsafe { PinUninit::from_ptr(ptr) };
+        let init = f(uninit);
+        mem::forget(init);
+        // SAFETY: The existance of `init` ensures the value has been initialized.
+        let own = unsafe { Self::from_raw(ptr) };
+        // SAFETY: The value was pinned since its initialization and the caller ensures
+        // it will be dropped before its memory is reused.
+        unsafe { Pin::new_unchecked(own) }
+    }
 }
 
 impl<'storage, T> Own<'storage, T> {
+    /// Creates a new `Own<'storage, T>` by moving `value` into the memory
+    /// represented by `storage`.
+    pub fn new<S>(storage: &'storage mut S, value: T) -> Self
+    where
+        S: Storage<T> + ?Sized,
+    {
+        Self::new_with(storage, |uninit| uninit.init(value))
+    }
+
     /// Consumes `this`, returning the pointed value.
     pub fn into_inner(this: Self) -> T {
         let this = ManuallyDrop::new(this);
@@ -72,6 +140,16 @@ impl<'storage, T> Own<'storage, T> {
     }
 }
 
+impl<'storage, T: ProjMove + ?Sized> Own<'storage, T> {
+    /// Destructures `this` into an `Own` for each of its fields, allowing them to be
+    /// moved out independently while the others are still dropped correctly.
+    ///
+    /// See also [`ProjMove`] and its derive macro, available with the `derive` feature.
+    pub fn into_proj(this: Self) -> OwnProj<'storage, T> {
+        T::proj_move(this)
+    }
+}
+
 impl<'storage, T: ?Sized> Drop for Own<'storage, T> {
     fn drop(&mut self) {
         // SAFETY: `Own<T>` logically owns the `T`
@@ -114,6 +192,4 @@ impl<'storage, T: ?Sized> BorrowMut<T> for Own<'storage, T> {
 
 // TODO:
 // - Box's traits
-// - Destructuring projections
-// - Partial Moves
 // - Slice draining/other?
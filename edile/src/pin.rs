@@ -0,0 +1,168 @@
+use crate::{Init, Uninit};
+
+use core::any::type_name;
+use core::fmt;
+use core::pin::Pin;
+use core::ptr::{self, NonNull};
+
+/// Represents some unitialized place that will be pinned once initialized.
+///
+/// Unlike [`Uninit<'a, T>`], initializing a `PinUninit<'a, T>` results in a
+/// [`Pin<Init<'a, T>>`], so the value can rely on its address during and after
+/// its initialization.
+///
+/// [`Uninit<'a, T>`]: Uninit
+/// [`Pin<Init<'a, T>>`]: Init::into_pin
+pub struct PinUninit<'a, T: ?Sized> {
+    uninit: Uninit<'a, T>,
+}
+
+impl<'a, T> PinUninit<'a, T> {
+    /// Initializes `self` with the given `value`.
+    ///
+    /// Returns a pinned token that guarantees this place has been initialized.
+    pub fn init(self, value: T) -> Pin<Init<'a, T>> {
+        Init::into_pin(self.uninit.init(value))
+    }
+}
+
+impl<'a, T: ?Sized> PinUninit<'a, T> {
+    /// Initializes `self` with the given constructor `f`.
+    ///
+    /// See also [`pin_proj_fn`] for constructors other than plain closures.
+    ///
+    /// [`pin_proj_fn`]: crate::pin_proj_fn
+    pub fn init_with<F>(self, f: F) -> Pin<Init<'a, T>>
+    where
+        F: FnOnce(PinUninit<'_, T>) -> Pin<Init<'_, T>>,
+    {
+        f(self)
+    }
+
+    /// Assumes `self` has been initialized, returning a [`Pin<Init<T>>`] token that
+    /// guarantees this place has been initialized.
+    ///
+    /// # Safety
+    /// The data pointed by `self` must have been initialized prior to this method call.
+    /// See also [`Uninit::assume_init`].
+    ///
+    /// [`Pin<Init<T>>`]: Init::into_pin
+    pub unsafe fn assume_init(self) -> Pin<Init<'a, T>> {
+        // SAFETY: The caller ensures the data has been initialized.
+        Init::into_pin(unsafe { self.uninit.assume_init() })
+    }
+
+    /// Creates a new `PinUninit<T>` from a raw pointer.
+    ///
+    /// # Safety
+    /// Same as [`Uninit::from_ptr`]. Moreover the memory pointed by `data` must
+    /// not be invalidated or reused before the value is dropped once initialized.
+    pub unsafe fn from_ptr(data: *mut T) -> PinUninit<'a, T> {
+        // SAFETY: The caller upholds the requirements of `Uninit::from_ptr`.
+        let uninit = unsafe { Uninit::from_ptr(data) };
+        Self { uninit }
+    }
+
+    /// Returns a pointer to the data pointed by `self`.
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.uninit.as_mut_ptr()
+    }
+
+    /// Returns the address of the place pointed by `self`, which is guaranteed to be
+    /// the address of the value once initialized.
+    ///
+    /// This can be used to store the address of the value, or of one of its structurally
+    /// pinned fields through its projection, in the value itself.
+    pub fn addr(&self) -> StableAddr<T> {
+        StableAddr::new(self.uninit.as_non_null())
+    }
+}
+
+/// Trait for the destructor of types whose fields are structurally pinned, which
+/// receives a pinned reference to the value instead of a `&mut Self`.
+///
+/// Types deriving `PinProjConstruct` can't implement [`Drop`], since its `&mut Self`
+/// would allow moving out of the pinned fields. They can instead be marked with
+/// `#[pin(PinnedDrop)]` and implement this trait, which will be called whenever
+/// the value is dropped, e.g. by an [`Init`] or an [`Own`]. The derive fails to
+/// compile if the attribute is present without the implementation or vice versa.
+///
+/// [`Own`]: crate::Own
+pub trait PinnedDrop {
+    /// Runs the destructor of the value.
+    ///
+    /// Like [`Drop::drop`], this is called once before the value is dropped, and it
+    /// can't be called explicitly, since only the `Drop` implementation generated by
+    /// the derive can create a [`PinnedDropToken`].
+    fn drop(self: Pin<&mut Self>, token: PinnedDropToken);
+}
+
+/// The token passed to [`PinnedDrop::drop`], ensuring it's only called when the
+/// value is dropped.
+pub struct PinnedDropToken {
+    _private: (),
+}
+
+impl PinnedDropToken {
+    /// Creates a new `PinnedDropToken`.
+    pub(crate) fn new() -> Self {
+        Self { _private: () }
+    }
+}
+
+/// The address of a place that won't move once initialized, obtained through
+/// [`PinUninit::addr`] or passed to the constructor of [`RcExt::new_cyclic_with`].
+///
+/// This can't be dereferenced, since the value may not have been initialized yet
+/// or may have already been dropped, but it can be converted into a raw pointer.
+///
+/// [`RcExt::new_cyclic_with`]: crate::RcExt::new_cyclic_with
+pub struct StableAddr<T: ?Sized> {
+    ptr: NonNull<T>,
+}
+
+// SAFETY: A `StableAddr` can't be dereferenced without `unsafe`, so it's just an address.
+unsafe impl<T: ?Sized> Send for StableAddr<T> {}
+// SAFETY: Same as above.
+unsafe impl<T: ?Sized> Sync for StableAddr<T> {}
+
+impl<T: ?Sized> StableAddr<T> {
+    /// Creates a new `StableAddr<T>` from the address of a place that won't move.
+    pub(crate) fn new(ptr: NonNull<T>) -> Self {
+        Self { ptr }
+    }
+
+    /// Returns a raw pointer to the place. It is valid for reads and writes only
+    /// while the value is alive, and writes must not move the value.
+    pub fn as_ptr(self) -> *mut T {
+        self.ptr.as_ptr()
+    }
+}
+
+impl<T: ?Sized> Clone for StableAddr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: ?Sized> Copy for StableAddr<T> {}
+
+impl<T: ?Sized> PartialEq for StableAddr<T> {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.ptr.as_ptr(), other.ptr.as_ptr())
+    }
+}
+
+impl<T: ?Sized> Eq for StableAddr<T> {}
+
+impl<T: ?Sized> fmt::Debug for StableAddr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Pointer::fmt(&self.ptr, f)
+    }
+}
+
+impl<'a, T: ?Sized> fmt::Debug for PinUninit<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad(type_name::<Self>())
+    }
+}
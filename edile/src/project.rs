@@ -1,5 +1,8 @@
 use crate::__private::Lt;
-use crate::{Init, Uninit};
+use crate::{Init, Own, PinUninit, Uninit};
+
+use core::convert::Infallible;
+use core::pin::Pin;
 
 /// Trait for types which can project an [`Uninit<'a, Self>`] field by field.
 ///
@@ -48,13 +51,181 @@ pub type InitProj<'a, T> = <T as WithInitProj<'a>>::InitProj;
 /// an initial [`Uninit`] guarantees that such [`Uninit`] has been initialized and can be
 /// [`assume_init`]ed.
 ///
+/// Implementors only need to provide [`try_proj_construct`], since [`proj_construct`]
+/// is implemented on top of it. Note that this is a breaking change for manual
+/// implementations written when [`proj_construct`] was the required method: they
+/// must implement [`try_proj_construct`] instead, as it can't be implemented in terms
+/// of [`proj_construct`] without a way to stop `f` early when it fails.
+///
 /// [`assume_init`]: Uninit::assume_init
+/// [`proj_construct`]: ProjConstruct::proj_construct
+/// [`try_proj_construct`]: ProjConstruct::try_proj_construct
 pub trait ProjConstruct: for<'a> WithInitProj<'a> {
     /// Initializes `uninit` by projecting it field by field and using
     /// `f` to initialize each field.
     fn proj_construct<F>(uninit: Uninit<'_, Self>, f: F) -> Init<'_, Self>
     where
-        F: for<'a> FnOnce(Lt<'a>, UninitProj<'a, Self>) -> InitProj<'a, Self>;
+        F: for<'a> FnOnce(Lt<'a>, UninitProj<'a, Self>) -> InitProj<'a, Self>,
+    {
+        match Self::try_proj_construct(uninit, |lt, proj| Ok::<_, Infallible>(f(lt, proj))) {
+            Ok(init) => init,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Like [`proj_construct`], but `f` may fail. In that case the error is returned
+    /// and `uninit` is left uninitialized, while the fields that `f` already initialized
+    /// are dropped along with their [`Init`]s.
+    ///
+    /// [`proj_construct`]: ProjConstruct::proj_construct
+    fn try_proj_construct<F, E>(uninit: Uninit<'_, Self>, f: F) -> Result<Init<'_, Self>, E>
+    where
+        F: for<'a> FnOnce(Lt<'a>, UninitProj<'a, Self>) -> Result<InitProj<'a, Self>, E>;
+}
+
+/// Trait for types which can project a [`PinUninit<'a, Self>`] field by field.
+///
+/// The `T` generic parameter is just used for a hacky workaround for HRTB
+/// related issues and should always default to `&'a Self`.
+///
+/// This trait is not intended to be accessed directly by the user. Use
+/// [`PinUninitProj`] or [`PinProjConstruct`].
+///
+/// [`PinUninit<'a, Self>`]: PinUninit
+#[doc(hidden)]
+pub trait WithPinUninitProj<'a, T = &'a Self> {
+    /// The projection itself. This can be used more ergonomically through [`PinUninitProj`].
+    type PinUninitProj: 'a;
+}
+
+/// Trait for types which can project a [`Pin<Init<'a, Self>>`] field by field.
+///
+/// The `T` generic parameter is just used for a hacky workaround for HRTB
+/// related issues and should always default to `&'a Self`.
+///
+/// This trait is not intended to be accessed directly by the user. Use
+/// [`PinInitProj`] or [`PinProjConstruct`].
+///
+/// [`Pin<Init<'a, Self>>`]: Init::into_pin
+#[doc(hidden)]
+pub trait WithPinInitProj<'a, T = &'a Self>: WithPinUninitProj<'a, T> {
+    /// The projection itself. This can be used more ergonomically through [`PinInitProj`].
+    type PinInitProj: 'a;
+}
+
+/// Represents the projection field by field of a [`PinUninit<'a, T>`].
+///
+/// Structurally pinned fields are projected to [`PinUninit`]s, while the others
+/// are projected to [`Uninit`]s.
+///
+/// [`PinUninit<'a, T>`]: PinUninit
+pub type PinUninitProj<'a, T> = <T as WithPinUninitProj<'a>>::PinUninitProj;
+
+/// Represents the projection field by field of a [`Pin<Init<'a, T>>`].
+///
+/// Structurally pinned fields are projected to [`Pin<Init>`]s, while the others
+/// are projected to [`Init`]s.
+///
+/// [`Pin<Init<'a, T>>`]: Init::into_pin
+/// [`Pin<Init>`]: Init::into_pin
+pub type PinInitProj<'a, T> = <T as WithPinInitProj<'a>>::PinInitProj;
+
+/// Trait for types that can be initialized field by field in a place
+/// where they will be pinned.
+///
+/// This is the pinned counterpart of [`ProjConstruct`], with the same guarantees
+/// between [`PinUninitProj`] and [`PinInitProj`].
+pub trait PinProjConstruct: for<'a> WithPinInitProj<'a> {
+    /// Initializes `uninit` by projecting it field by field and using
+    /// `f` to initialize each field.
+    fn pin_proj_construct<F>(uninit: PinUninit<'_, Self>, f: F) -> Pin<Init<'_, Self>>
+    where
+        F: for<'a> FnOnce(Lt<'a>, PinUninitProj<'a, Self>) -> PinInitProj<'a, Self>;
+}
+
+/// Trait for types which can project an [`Own<'a, Self>`] field by field.
+///
+/// The `T` generic parameter is just used for a hacky workaround for HRTB
+/// related issues and should always default to `&'a Self`.
+///
+/// This trait is not intended to be accessed directly by the user. Use
+/// [`OwnProj`] or [`ProjMove`].
+///
+/// [`Own<'a, Self>`]: Own
+#[doc(hidden)]
+pub trait WithOwnProj<'a, T = &'a Self> {
+    /// The projection itself. This can be used more ergonomically through [`OwnProj`].
+    type OwnProj: 'a;
+}
+
+/// Represents the projection field by field of an [`Own<'a, T>`].
+///
+/// [`Own<'a, T>`]: Own
+pub type OwnProj<'a, T> = <T as WithOwnProj<'a>>::OwnProj;
+
+/// Trait for types whose fields can be moved out of an [`Own`] independently.
+///
+/// This is usually used through [`Own::into_proj`].
+pub trait ProjMove: for<'a> WithOwnProj<'a> {
+    /// Destructures `this` into an [`Own`] for each of its fields.
+    fn proj_move(this: Own<'_, Self>) -> OwnProj<'_, Self>;
+}
+
+/// Trait for types which can project a `&'a mut Self` field by field.
+///
+/// The `T` generic parameter is just used for a hacky workaround for HRTB
+/// related issues and should always default to `&'a Self`.
+///
+/// This trait is not intended to be accessed directly by the user. Use
+/// [`MutProj`] or [`ProjMut`].
+#[doc(hidden)]
+pub trait WithMutProj<'a, T = &'a Self> {
+    /// The projection itself. This can be used more ergonomically through [`MutProj`].
+    type MutProj: 'a;
+}
+
+/// Represents the projection field by field of a `&'a mut T`.
+pub type MutProj<'a, T> = <T as WithMutProj<'a>>::MutProj;
+
+/// Trait for types whose fields can be mutably borrowed at the same time.
+///
+/// Since [`Init`] and [`Own`] implement [`DerefMut`], [`project_mut`] can be
+/// called directly on them.
+///
+/// [`DerefMut`]: core::ops::DerefMut
+/// [`project_mut`]: ProjMut::project_mut
+pub trait ProjMut: for<'a> WithMutProj<'a> {
+    /// Mutably borrows each field of `self`.
+    fn project_mut(&mut self) -> MutProj<'_, Self>;
+}
+
+/// Trait for types which can project a `Pin<&'a mut Self>` field by field.
+///
+/// The `T` generic parameter is just used for a hacky workaround for HRTB
+/// related issues and should always default to `&'a Self`.
+///
+/// This trait is not intended to be accessed directly by the user. Use
+/// [`PinMutProj`] or [`PinProjMut`].
+#[doc(hidden)]
+pub trait WithPinMutProj<'a, T = &'a Self> {
+    /// The projection itself. This can be used more ergonomically through [`PinMutProj`].
+    type PinMutProj: 'a;
+}
+
+/// Represents the projection field by field of a `Pin<&'a mut T>`.
+///
+/// Structurally pinned fields are projected to `Pin<&'a mut _>`s, while the others
+/// are projected to `&'a mut _`s.
+pub type PinMutProj<'a, T> = <T as WithPinMutProj<'a>>::PinMutProj;
+
+/// Trait for types whose fields can be mutably borrowed at the same time
+/// while the type is pinned.
+///
+/// A `Pin<Own<T>>` or a `Pin<Init<T>>` can be projected by first calling
+/// [`Pin::as_mut`] on it.
+pub trait PinProjMut: for<'a> WithPinMutProj<'a> {
+    /// Mutably borrows each field of `self`, keeping the structurally pinned ones pinned.
+    fn project_pin(self: Pin<&mut Self>) -> PinMutProj<'_, Self>;
 }
 
 mod tuples {
@@ -69,9 +240,12 @@ mod tuples {
         }
 
         impl<$($ty),+> ProjConstruct for ($($ty,)+) {
-            fn proj_construct<InitFn>(mut uninit: Uninit<'_, Self>, f: InitFn) -> Init<'_, Self>
+            fn try_proj_construct<InitFn, InitErr>(
+                mut uninit: Uninit<'_, Self>,
+                f: InitFn,
+            ) -> Result<Init<'_, Self>, InitErr>
             where
-                InitFn: for<'a> FnOnce(Lt<'a>, UninitProj<'a, Self>) -> InitProj<'a, Self>
+                InitFn: for<'a> FnOnce(Lt<'a>, UninitProj<'a, Self>) -> Result<InitProj<'a, Self>, InitErr>
             {
                 use core::ptr::addr_of_mut;
                 let ptr = uninit.as_mut_ptr();
@@ -81,10 +255,50 @@ mod tuples {
                 // so the field ones are valid too. Moreover we never expose the lifetime of
                 // the `Uninit`s created, so `from_ptr` is safe to call.
                 let uninit_proj = unsafe { ($(Uninit::from_ptr(addr_of_mut!((*ptr).$idx)),)+) };
-                let init_proj = f(Lt::default(), uninit_proj);
+                let init_proj = f(Lt::default(), uninit_proj)?;
                 ::core::mem::forget(init_proj);
                 // SAFETY: The existance of `init_proj` ensures each field has been initialized.
-                unsafe { uninit.assume_init() }
+                Ok(unsafe { uninit.assume_init() })
+            }
+        }
+
+        impl<'a, $($ty),*> WithMutProj<'a> for ($($ty,)+) {
+            type MutProj = ($(&'a mut $ty,)+);
+        }
+
+        impl<$($ty),+> ProjMut for ($($ty,)+) {
+            fn project_mut(&mut self) -> MutProj<'_, Self> {
+                ($(&mut self.$idx,)+)
+            }
+        }
+
+        impl<'a, $($ty),*> WithPinMutProj<'a> for ($($ty,)+) {
+            type PinMutProj = ($(Pin<&'a mut $ty>,)+);
+        }
+
+        impl<$($ty),+> PinProjMut for ($($ty,)+) {
+            fn project_pin(self: Pin<&mut Self>) -> PinMutProj<'_, Self> {
+                // SAFETY: Every element of a tuple is structurally pinned: tuples are `Unpin`
+                // only if all their elements are, don't implement `Drop` and are never
+                // packed, so the elements are never moved out of the pin.
+                let this = unsafe { Pin::get_unchecked_mut(self) };
+                // SAFETY: Same as above.
+                unsafe { ($(Pin::new_unchecked(&mut this.$idx),)+) }
+            }
+        }
+
+        impl<'a, $($ty),*> WithOwnProj<'a> for ($($ty,)+) {
+            type OwnProj = ($(Own<'a, $ty>,)+);
+        }
+
+        impl<$($ty),+> ProjMove for ($($ty,)+) {
+            fn proj_move(this: Own<'_, Self>) -> OwnProj<'_, Self> {
+                use core::ptr::addr_of_mut;
+                let ptr: *mut Self = Own::leak(this);
+                // SAFETY: `ptr` comes from a reference, so it's valid and the field ones are
+                // valid too. The data was owned by `this`, which we leaked, so the ownership
+                // of each field can be transferred to the new `Own`s.
+                unsafe { ($(Own::from_raw(addr_of_mut!((*ptr).$idx)),)+) }
             }
         }
     }
@@ -103,9 +317,12 @@ mod arrays {
     }
 
     impl<T, const N: usize> ProjConstruct for [T; N] {
-        fn proj_construct<InitFn>(mut uninit: Uninit<'_, Self>, f: InitFn) -> Init<'_, Self>
+        fn try_proj_construct<InitFn, E>(
+            mut uninit: Uninit<'_, Self>,
+            f: InitFn,
+        ) -> Result<Init<'_, Self>, E>
         where
-            InitFn: for<'a> FnOnce(Lt<'a>, UninitProj<'a, Self>) -> InitProj<'a, Self>,
+            InitFn: for<'a> FnOnce(Lt<'a>, UninitProj<'a, Self>) -> Result<InitProj<'a, Self>, E>,
         {
             let mut ptr = uninit.as_mut_ptr().cast::<T>();
             let uninit_proj = [(); N].map(|_| {
@@ -119,10 +336,51 @@ mod arrays {
                 ptr = unsafe { ptr.add(1) };
                 uninit
             });
-            let init_proj = f(Lt::default(), uninit_proj);
+            let init_proj = f(Lt::default(), uninit_proj)?;
             mem::forget(init_proj);
             // SAFETY: The existance of `init_proj` ensures each element has been initialized.
-            unsafe { uninit.assume_init() }
+            Ok(unsafe { uninit.assume_init() })
+        }
+    }
+
+    impl<'a, T, const N: usize> WithMutProj<'a> for [T; N] {
+        type MutProj = [&'a mut T; N];
+    }
+
+    impl<T, const N: usize> ProjMut for [T; N] {
+        fn project_mut(&mut self) -> MutProj<'_, Self> {
+            self.each_mut()
+        }
+    }
+
+    impl<'a, T, const N: usize> WithPinMutProj<'a> for [T; N] {
+        type PinMutProj = [Pin<&'a mut T>; N];
+    }
+
+    impl<T, const N: usize> PinProjMut for [T; N] {
+        fn project_pin(self: Pin<&mut Self>) -> PinMutProj<'_, Self> {
+            // SAFETY: Every element of an array is structurally pinned: arrays are `Unpin`
+            // only if their elements are and don't implement `Drop`, so the elements are
+            // never moved out of the pin.
+            let this = unsafe { Pin::get_unchecked_mut(self) };
+            // SAFETY: Same as above.
+            this.each_mut()
+                .map(|elem| unsafe { Pin::new_unchecked(elem) })
+        }
+    }
+
+    impl<'a, T, const N: usize> WithOwnProj<'a> for [T; N] {
+        type OwnProj = [Own<'a, T>; N];
+    }
+
+    impl<T, const N: usize> ProjMove for [T; N] {
+        fn proj_move(this: Own<'_, Self>) -> OwnProj<'_, Self> {
+            // SAFETY: Each element comes from a reference, so it's valid. The data was
+            // owned by `this`, which we leaked, so the ownership of each element can be
+            // transferred to the new `Own`s.
+            Own::leak(this)
+                .each_mut()
+                .map(|elem| unsafe { Own::from_raw(elem) })
         }
     }
 }
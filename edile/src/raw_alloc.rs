@@ -0,0 +1,322 @@
+use crate::{Init, PinUninit, Uninit};
+
+use core::alloc::Layout;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::{self, ManuallyDrop};
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use core::ptr::{self, NonNull};
+
+/// The error returned by a [`RawAlloc`] when it fails to allocate some memory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AllocError;
+
+impl fmt::Display for AllocError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("memory allocation failed")
+    }
+}
+
+/// A minimal allocator interface, usable on stable as an alternative
+/// to `core::alloc::Allocator`.
+///
+/// # Safety
+/// - The memory returned by [`allocate`] must be valid for reads and writes of
+///   `layout.size()` bytes, aligned to `layout.align()` and not used by anything
+///   else until it's passed to [`deallocate`] or the allocator is dropped.
+/// - Moving the allocator must not invalidate the memory it returned.
+///
+/// [`allocate`]: RawAlloc::allocate
+/// [`deallocate`]: RawAlloc::deallocate
+pub unsafe trait RawAlloc {
+    /// Allocates some memory fitting the given `layout`, which may have a size of zero.
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError>;
+
+    /// Deallocates the memory pointed by `ptr`.
+    ///
+    /// # Safety
+    /// `ptr` must have been returned by a call to [`allocate`] on this allocator
+    /// with the same `layout`, and must not have been deallocated yet.
+    ///
+    /// [`allocate`]: RawAlloc::allocate
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+}
+
+// SAFETY: Shared references to an allocator return the same memory as the allocator.
+unsafe impl<A: RawAlloc + ?Sized> RawAlloc for &A {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        (**self).allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        // SAFETY: The caller upholds the requirements of `A::deallocate`.
+        unsafe { (**self).deallocate(ptr, layout) }
+    }
+}
+
+/// The global memory allocator, i.e. the one registered with `#[global_allocator]`.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Global;
+
+// SAFETY: The memory is allocated by the global allocator, which upholds the same requirements.
+// Zero sized allocations return a dangling but aligned pointer and are never deallocated.
+#[cfg(feature = "alloc")]
+unsafe impl RawAlloc for Global {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        if layout.size() == 0 {
+            return Ok(dangling(layout));
+        }
+        // SAFETY: `layout` has a non-zero size.
+        let ptr = unsafe { alloc::alloc::alloc(layout) };
+        NonNull::new(ptr).ok_or(AllocError)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() != 0 {
+            // SAFETY: The caller ensures `ptr` was allocated with `layout`, which has
+            // a non-zero size, so it comes from `alloc::alloc::alloc`.
+            unsafe { alloc::alloc::dealloc(ptr.as_ptr(), layout) }
+        }
+    }
+}
+
+/// Returns a dangling pointer aligned for `layout`.
+#[cfg(feature = "alloc")]
+fn dangling(layout: Layout) -> NonNull<u8> {
+    // SAFETY: The alignment of a `Layout` is never zero.
+    unsafe { NonNull::new_unchecked(ptr::without_provenance_mut(layout.align())) }
+}
+
+/// Handles the failure of an allocation with the given `layout`.
+fn alloc_failed(layout: Layout) -> ! {
+    #[cfg(feature = "alloc")]
+    alloc::alloc::handle_alloc_error(layout);
+    #[cfg(not(feature = "alloc"))]
+    panic!("memory allocation of {} bytes failed", layout.size());
+}
+
+/// An owning pointer to a value allocated with the allocator `A`, similar to
+/// a `Box<T, A>` but usable on stable.
+pub struct AllocBox<T: ?Sized, A: RawAlloc> {
+    ptr: NonNull<T>,
+    alloc: A,
+    _phantom_owned: PhantomData<T>,
+}
+
+// SAFETY: An `AllocBox` owns its value and its allocator, like a `(T, A)` would.
+unsafe impl<T: ?Sized + Send, A: RawAlloc + Send> Send for AllocBox<T, A> {}
+// SAFETY: Same as above.
+unsafe impl<T: ?Sized + Sync, A: RawAlloc + Sync> Sync for AllocBox<T, A> {}
+
+impl<T, A: RawAlloc> AllocBox<T, A> {
+    /// Allocates some memory with `alloc` and initializes it with the given
+    /// constructor `f`, without the value ever being on the stack.
+    ///
+    /// If `f` panics the memory is deallocated.
+    ///
+    /// # Panics
+    /// Handles allocation failures like `Box` does, or panics without the `alloc` feature.
+    pub fn new_in_with<F>(alloc: A, f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+    {
+        Self::try_new_in_with(alloc, f)
+            .unwrap_or_else(|AllocError| alloc_failed(Layout::new::<T>()))
+    }
+
+    /// Like [`new_in_with`], but returns an error if the allocation fails, in which
+    /// case `f` is not called.
+    ///
+    /// [`new_in_with`]: AllocBox::new_in_with
+    pub fn try_new_in_with<F>(alloc: A, f: F) -> Result<Self, AllocError>
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+    {
+        let layout = Layout::new::<T>();
+        let ptr = alloc.allocate(layout)?.cast::<T>();
+        // SAFETY: `ptr` was just allocated with the layout of `T`.
+        Ok(unsafe { Self::init_raw(ptr, layout, alloc, f) })
+    }
+
+    /// Like [`new_in_with`], but the value will be pinned, so `f` can rely on its
+    /// final address.
+    ///
+    /// If `f` panics the memory and `alloc` are leaked, since the fields it initialized
+    /// may have been pinned and forgotten, and their memory must not be reused without
+    /// dropping them.
+    ///
+    /// Like [`AllocBox::into_pin`], this requires `A: 'static`.
+    ///
+    /// # Panics
+    /// Handles allocation failures like [`new_in_with`] does.
+    ///
+    /// [`new_in_with`]: AllocBox::new_in_with
+    pub fn pin_in_with<F>(alloc: A, f: F) -> Pin<Self>
+    where
+        A: 'static,
+        F: FnOnce(PinUninit<'_, T>) -> Pin<Init<'_, T>>,
+    {
+        // Dropping `alloc` may invalidate the memory too, so it must be leaked as well.
+        let alloc = ManuallyDrop::new(alloc);
+        let layout = Layout::new::<T>();
+        let ptr = alloc
+            .allocate(layout)
+            .unwrap_or_else(|AllocError| alloc_failed(layout))
+            .cast::<T>();
+        // SAFETY: `ptr` was just allocated with the layout of `T`, so it's valid and aligned.
+        // Moreover we never expose the lifetime of the `Uninit` created, so `from_ptr` is
+        // safe to call.
+        let uninit = unsafe { Uninit::from_ptr(ptr.as_ptr()) };
+        // SAFETY: The memory is never deallocated before the value is dropped, since it's
+        // leaked if `f` panics and `A: 'static` ensures the memory of a leaked `AllocBox`
+        // can't be reclaimed by the owner of a borrowed allocator.
+        let init = unsafe { uninit.pin_init_with(f) };
+        mem::forget(init);
+        // The existance of `init` ensures the value has been initialized,
+        // so the `AllocBox` can own it.
+        let this = Self {
+            ptr,
+            alloc: ManuallyDrop::into_inner(alloc),
+            _phantom_owned: PhantomData,
+        };
+        AllocBox::into_pin(this)
+    }
+}
+
+impl<T, A: RawAlloc> AllocBox<[T], A> {
+    /// Allocates a slice of `len` elements with `alloc` and initializes it with
+    /// the given constructor `f`, for example [`slice_each`].
+    ///
+    /// If `f` panics the memory is deallocated.
+    ///
+    /// # Panics
+    /// Panics if the size of the slice overflows, and handles allocation failures
+    /// like [`new_in_with`] does.
+    ///
+    /// [`new_in_with`]: AllocBox::new_in_with
+    /// [`slice_each`]: crate::slice_each
+    pub fn new_slice_in_with<F>(len: usize, alloc: A, f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, [T]>) -> Init<'_, [T]>,
+    {
+        let layout = Layout::array::<T>(len).expect("capacity overflow");
+        Self::try_new_slice_in_with(len, alloc, f).unwrap_or_else(|AllocError| alloc_failed(layout))
+    }
+
+    /// Like [`new_slice_in_with`], but returns an error if the size of the slice overflows
+    /// or the allocation fails, in which case `f` is not called.
+    ///
+    /// [`new_slice_in_with`]: AllocBox::new_slice_in_with
+    pub fn try_new_slice_in_with<F>(len: usize, alloc: A, f: F) -> Result<Self, AllocError>
+    where
+        F: FnOnce(Uninit<'_, [T]>) -> Init<'_, [T]>,
+    {
+        let layout = Layout::array::<T>(len).map_err(|_| AllocError)?;
+        let ptr = alloc.allocate(layout)?.cast::<T>();
+        let ptr = NonNull::slice_from_raw_parts(ptr, len);
+        // SAFETY: `ptr` was just allocated with the layout of `[T]` with `len` elements.
+        Ok(unsafe { Self::init_raw(ptr, layout, alloc, f) })
+    }
+}
+
+impl<T: ?Sized, A: RawAlloc> AllocBox<T, A> {
+    /// Initializes the memory pointed by `ptr` with `f`, deallocating it if `f` panics.
+    ///
+    /// # Safety
+    /// `ptr` must have been allocated by `alloc` with the given `layout`, which must be
+    /// the layout of the value it points to.
+    unsafe fn init_raw<F>(ptr: NonNull<T>, layout: Layout, alloc: A, f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+    {
+        // Invariant: `ptr` was allocated by `alloc` with `layout`.
+        struct DeallocOnPanic<'a, A: RawAlloc> {
+            ptr: NonNull<u8>,
+            layout: Layout,
+            alloc: &'a A,
+        }
+
+        impl<A: RawAlloc> Drop for DeallocOnPanic<'_, A> {
+            fn drop(&mut self) {
+                // SAFETY: for `DeallocOnPanic`'s invariant `ptr` was allocated by `alloc`
+                // with `layout`, and it wasn't deallocated since the guard is only dropped
+                // on panic.
+                unsafe { self.alloc.deallocate(self.ptr, self.layout) };
+            }
+        }
+
+        let guard = DeallocOnPanic {
+            ptr: ptr.cast::<u8>(),
+            layout,
+            alloc: &alloc,
+        };
+        // SAFETY: The caller ensures `ptr` is valid and aligned. Moreover we never expose
+        // the lifetime of the `Uninit` created, so `from_ptr` is safe to call.
+        let uninit = unsafe { Uninit::from_ptr(ptr.as_ptr()) };
+        let init = f(uninit);
+        mem::forget(init);
+        mem::forget(guard);
+        // The existance of `init` ensures the value has been initialized,
+        // so the `AllocBox` can own it.
+        Self {
+            ptr,
+            alloc,
+            _phantom_owned: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the allocator of `this`.
+    pub fn allocator(this: &Self) -> &A {
+        &this.alloc
+    }
+
+    /// Converts an `AllocBox<T, A>` into a `Pin<AllocBox<T, A>>`.
+    ///
+    /// Like `Box::into_pin` this requires `A: 'static`, since otherwise the
+    /// `Pin<AllocBox<T, A>>` could be leaked and the memory reused by the owner of
+    /// a borrowed allocator, e.g. by resetting an arena, without dropping the value.
+    pub fn into_pin(this: Self) -> Pin<Self>
+    where
+        A: 'static,
+    {
+        // SAFETY: The value of an `AllocBox` is never moved out of its memory
+        // through a `Pin<AllocBox<T, A>>`, and it's dropped before its memory
+        // is deallocated, or never deallocated if the `AllocBox` is leaked,
+        // since `A: 'static` can't borrow the memory from someone else.
+        unsafe { Pin::new_unchecked(this) }
+    }
+}
+
+impl<T: ?Sized, A: RawAlloc> Drop for AllocBox<T, A> {
+    fn drop(&mut self) {
+        // SAFETY: `self.ptr` points to a valid value owned by `self`.
+        let layout = unsafe { Layout::for_value(self.ptr.as_ref()) };
+        // SAFETY: Same as above.
+        unsafe { ptr::drop_in_place(self.ptr.as_ptr()) };
+        // SAFETY: `self.ptr` was allocated by `self.alloc` with the layout of its value.
+        unsafe { self.alloc.deallocate(self.ptr.cast::<u8>(), layout) };
+    }
+}
+
+impl<T: ?Sized, A: RawAlloc> Deref for AllocBox<T, A> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: `self.ptr` points to a valid value owned by `self`.
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T: ?Sized, A: RawAlloc> DerefMut for AllocBox<T, A> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: `self.ptr` points to a valid value owned by `self`.
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T: ?Sized + fmt::Debug, A: RawAlloc> fmt::Debug for AllocBox<T, A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{Init, PinUninit, StableAddr, Uninit};
+
+use alloc::rc::Rc;
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::pin::Pin;
+use core::ptr::NonNull;
+
+#[cfg(feature = "nightly")]
+use crate::AllocError;
+#[cfg(feature = "nightly")]
+use core::alloc::Allocator;
+
+/// Extension trait for constructing an [`Rc`] in place.
+pub trait RcExt<T>: Sized {
+    /// Allocates some memory on the heap and initializes it with the given
+    /// constructor `f`, without the value ever being on the stack.
+    ///
+    /// If `f` panics the memory is deallocated.
+    fn new_with<F>(f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>;
+
+    /// Like [`new_with`], but returns an error if the allocation fails, in which case
+    /// `f` is not called.
+    ///
+    /// Only available with the `nightly` feature, since handling allocation failures
+    /// of an `Rc` requires [`Rc::try_new_uninit`].
+    ///
+    /// [`new_with`]: RcExt::new_with
+    #[cfg(feature = "nightly")]
+    fn try_new_with<F>(f: F) -> Result<Self, AllocError>
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>;
+
+    /// Like [`new_with`], but `f` also receives the address the value will have in the
+    /// returned `Rc`, so that it can store it, e.g. in its children.
+    ///
+    /// Unlike [`Rc::new_cyclic`] the constructor doesn't receive a `Weak<T>`, since it
+    /// could be upgraded while the value is still uninitialized. A `Weak<T>` can be
+    /// created from the returned `Rc` with [`Rc::downgrade`] instead. The address stays
+    /// valid as long as the value is in the `Rc`, i.e. until it's dropped or moved out,
+    /// e.g. with [`Rc::try_unwrap`].
+    ///
+    /// If `f` panics the fields it initialized are dropped and the memory is deallocated,
+    /// so the address must not be used after that.
+    ///
+    /// [`new_with`]: RcExt::new_with
+    fn new_cyclic_with<F>(f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>, StableAddr<T>) -> Init<'_, T>;
+
+    /// Allocates some memory on the heap and initializes it with the given pinned
+    /// constructor `f`, which can thus rely on the final address of the value.
+    ///
+    /// If `f` panics the memory is leaked, since the fields it initialized may have been
+    /// pinned and forgotten, and their memory must not be reused without dropping them.
+    ///
+    /// See also [`pin_proj_fn`] for constructors other than plain closures.
+    ///
+    /// [`pin_proj_fn`]: crate::pin_proj_fn
+    fn pin_with<F>(f: F) -> Pin<Self>
+    where
+        F: FnOnce(PinUninit<'_, T>) -> Pin<Init<'_, T>>;
+}
+
+impl<T> RcExt<T> for Rc<T> {
+    fn new_with<F>(f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+    {
+        init_rc(Rc::<T>::new_uninit(), f)
+    }
+
+    #[cfg(feature = "nightly")]
+    fn try_new_with<F>(f: F) -> Result<Self, AllocError>
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+    {
+        let rc = Rc::<T>::try_new_uninit().map_err(|_| AllocError)?;
+        Ok(init_rc(rc, f))
+    }
+
+    fn new_cyclic_with<F>(f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>, StableAddr<T>) -> Init<'_, T>,
+    {
+        let mut rc = Rc::<T>::new_uninit();
+        // Unlike `ptr` below this address doesn't come from a mutable reference, so it
+        // remains valid when the value is accessed through the `Rc`.
+        let addr = Rc::as_ptr(&rc).cast::<T>() as *mut T;
+        // SAFETY: The pointer of an `Rc` is never null.
+        let addr = StableAddr::new(unsafe { NonNull::new_unchecked(addr) });
+        // The `Rc` was just created, so it's unique and `get_mut` can't fail.
+        let ptr = Rc::get_mut(&mut rc).unwrap().as_mut_ptr();
+        // SAFETY: `ptr` comes from a mutable reference, so it's valid and aligned.
+        // Moreover we never expose the lifetime of the `Uninit` created, so `from_ptr`
+        // is safe to call.
+        let uninit = unsafe { Uninit::from_ptr(ptr) };
+        let init = f(uninit, addr);
+        mem::forget(init);
+        // SAFETY: The existance of `init` ensures the value has been initialized.
+        unsafe { rc.assume_init() }
+    }
+
+    fn pin_with<F>(f: F) -> Pin<Self>
+    where
+        F: FnOnce(PinUninit<'_, T>) -> Pin<Init<'_, T>>,
+    {
+        // If `f` panics the memory is leaked, since it may contain pinned fields that
+        // were never dropped.
+        let mut rc = ManuallyDrop::new(Rc::<T>::new_uninit());
+        // The `Rc` was just created, so it's unique and `get_mut` can't fail.
+        let ptr = Rc::get_mut(&mut *rc).unwrap().as_mut_ptr();
+        // SAFETY: `ptr` comes from a mutable reference, so it's valid and aligned. Moreover
+        // we never expose the lifetime of the `Uninit` created, so `from_ptr` is safe to call.
+        let uninit = unsafe { Uninit::from_ptr(ptr) };
+        // SAFETY: The memory will be pinned, i.e. not reused until the value is dropped,
+        // once initialized.
+        let init = unsafe { uninit.pin_init_with(f) };
+        mem::forget(init);
+        // SAFETY: The existance of `init` ensures the value has been initialized.
+        let rc = unsafe { ManuallyDrop::into_inner(rc).assume_init() };
+        // SAFETY: The value is never moved out of the allocation of an `Rc`, and it's
+        // dropped before the allocation is freed.
+        unsafe { Pin::new_unchecked(rc) }
+    }
+}
+
+/// Initializes the value of `rc`, which must be unique, with the constructor `f`.
+fn init_rc<T, F>(mut rc: Rc<MaybeUninit<T>>, f: F) -> Rc<T>
+where
+    F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+{
+    // The `Rc` is unique, so `get_mut` can't fail.
+    let ptr = Rc::get_mut(&mut rc).unwrap().as_mut_ptr();
+    // SAFETY: `ptr` comes from a mutable reference, so it's valid and aligned.
+    // Moreover we never expose the lifetime of the `Uninit` created, so `from_ptr`
+    // is safe to call.
+    let uninit = unsafe { Uninit::from_ptr(ptr) };
+    let init = f(uninit);
+    mem::forget(init);
+    // SAFETY: The existance of `init` ensures the value has been initialized.
+    unsafe { rc.assume_init() }
+}
+
+/// Extension trait for constructing an [`Rc`] of a slice in place.
+pub trait RcSliceExt<T> {
+    /// Allocates a slice of `len` elements on the heap and initializes it with
+    /// the given constructor `f`, for example [`slice_each`].
+    ///
+    /// If `f` panics the memory is deallocated.
+    ///
+    /// Unlike [`BoxSliceExt`] there's no fallible version of this method, not even
+    /// with the `nightly` feature, since the standard library provides no way to handle
+    /// allocation failures of an `Rc<[T]>`.
+    ///
+    /// [`slice_each`]: crate::slice_each
+    /// [`BoxSliceExt`]: crate::BoxSliceExt
+    fn new_slice_with<F>(len: usize, f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, [T]>) -> Init<'_, [T]>;
+}
+
+impl<T> RcSliceExt<T> for Rc<[T]> {
+    fn new_slice_with<F>(len: usize, f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, [T]>) -> Init<'_, [T]>,
+    {
+        let mut rc = Rc::<[T]>::new_uninit_slice(len);
+        // The `Rc` was just created, so it's unique and `get_mut` can't fail.
+        let slice = Rc::get_mut(&mut rc).unwrap();
+        // Can't use `cast` because it requires `U` to be `Sized`.
+        let ptr = slice as *mut [MaybeUninit<T>] as *mut [T];
+        // SAFETY: `ptr` comes from a mutable reference, so it's valid and aligned.
+        // Moreover we never expose the lifetime of the `Uninit` created, so `from_ptr`
+        // is safe to call.
+        let uninit = unsafe { Uninit::from_ptr(ptr) };
+        let init = f(uninit);
+        mem::forget(init);
+        // SAFETY: The existance of `init` ensures the elements have been initialized.
+        unsafe { rc.assume_init() }
+    }
+}
+
+/// Extension trait for constructing an [`Rc`] with a custom allocator in place.
+#[cfg(feature = "nightly")]
+pub trait RcAllocExt<T, A: Allocator> {
+    /// Allocates some memory with `alloc` and initializes it with the given
+    /// constructor `f`, without the value ever being on the stack.
+    ///
+    /// If `f` panics the memory is deallocated.
+    fn new_in_with<F>(alloc: A, f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>;
+}
+
+#[cfg(feature = "nightly")]
+impl<T, A: Allocator> RcAllocExt<T, A> for Rc<T, A> {
+    fn new_in_with<F>(alloc: A, f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+    {
+        let mut rc = Rc::<T, A>::new_uninit_in(alloc);
+        // The `Rc` was just created, so it's unique and `get_mut` can't fail.
+        let ptr = Rc::get_mut(&mut rc).unwrap().as_mut_ptr();
+        // SAFETY: `ptr` comes from a mutable reference, so it's valid and aligned.
+        // Moreover we never expose the lifetime of the `Uninit` created, so `from_ptr`
+        // is safe to call.
+        let uninit = unsafe { Uninit::from_ptr(ptr) };
+        let init = f(uninit);
+        mem::forget(init);
+        // SAFETY: The existance of `init` ensures the value has been initialized.
+        unsafe { rc.assume_init() }
+    }
+}
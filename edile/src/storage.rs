@@ -10,7 +10,7 @@ use core::mem::MaybeUninit;
 /// can't wrap `!Sized` types, in particular `[T]`. Users should probably
 /// not implement this trait.
 ///
-/// # Safety:
+/// # Safety
 /// [`as_mut_ptr`] returns a pointer that will be valid for the lifetime
 /// of its `&mut self` parameter.
 ///
@@ -0,0 +1,215 @@
+use crate::{Init, PinUninit, StableAddr, Uninit};
+
+use alloc::sync::Arc;
+use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::pin::Pin;
+use core::ptr::NonNull;
+
+#[cfg(feature = "nightly")]
+use crate::AllocError;
+#[cfg(feature = "nightly")]
+use core::alloc::Allocator;
+
+/// Extension trait for constructing an [`Arc`] in place.
+pub trait ArcExt<T>: Sized {
+    /// Allocates some memory on the heap and initializes it with the given
+    /// constructor `f`, without the value ever being on the stack.
+    ///
+    /// If `f` panics the memory is deallocated.
+    fn new_with<F>(f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>;
+
+    /// Like [`new_with`], but returns an error if the allocation fails, in which case
+    /// `f` is not called.
+    ///
+    /// Only available with the `nightly` feature, since handling allocation failures
+    /// of an `Arc` requires [`Arc::try_new_uninit`].
+    ///
+    /// [`new_with`]: ArcExt::new_with
+    #[cfg(feature = "nightly")]
+    fn try_new_with<F>(f: F) -> Result<Self, AllocError>
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>;
+
+    /// Like [`new_with`], but `f` also receives the address the value will have in the
+    /// returned `Arc`, so that it can store it, e.g. in its children.
+    ///
+    /// Unlike [`Arc::new_cyclic`] the constructor doesn't receive a `Weak<T>`, since it
+    /// could be upgraded while the value is still uninitialized. A `Weak<T>` can be
+    /// created from the returned `Arc` with [`Arc::downgrade`] instead. The address stays
+    /// valid as long as the value is in the `Arc`, i.e. until it's dropped or moved out,
+    /// e.g. with [`Arc::try_unwrap`].
+    ///
+    /// If `f` panics the fields it initialized are dropped and the memory is deallocated,
+    /// so the address must not be used after that.
+    ///
+    /// [`new_with`]: ArcExt::new_with
+    fn new_cyclic_with<F>(f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>, StableAddr<T>) -> Init<'_, T>;
+
+    /// Allocates some memory on the heap and initializes it with the given pinned
+    /// constructor `f`, which can thus rely on the final address of the value.
+    ///
+    /// If `f` panics the memory is leaked, since the fields it initialized may have been
+    /// pinned and forgotten, and their memory must not be reused without dropping them.
+    ///
+    /// See also [`pin_proj_fn`] for constructors other than plain closures.
+    ///
+    /// [`pin_proj_fn`]: crate::pin_proj_fn
+    fn pin_with<F>(f: F) -> Pin<Self>
+    where
+        F: FnOnce(PinUninit<'_, T>) -> Pin<Init<'_, T>>;
+}
+
+impl<T> ArcExt<T> for Arc<T> {
+    fn new_with<F>(f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+    {
+        init_arc(Arc::<T>::new_uninit(), f)
+    }
+
+    #[cfg(feature = "nightly")]
+    fn try_new_with<F>(f: F) -> Result<Self, AllocError>
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+    {
+        let arc = Arc::<T>::try_new_uninit().map_err(|_| AllocError)?;
+        Ok(init_arc(arc, f))
+    }
+
+    fn new_cyclic_with<F>(f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>, StableAddr<T>) -> Init<'_, T>,
+    {
+        let mut arc = Arc::<T>::new_uninit();
+        // Unlike `ptr` below this address doesn't come from a mutable reference, so it
+        // remains valid when the value is accessed through the `Arc`.
+        let addr = Arc::as_ptr(&arc).cast::<T>() as *mut T;
+        // SAFETY: The pointer of an `Arc` is never null.
+        let addr = StableAddr::new(unsafe { NonNull::new_unchecked(addr) });
+        // The `Arc` was just created, so it's unique and `get_mut` can't fail.
+        let ptr = Arc::get_mut(&mut arc).unwrap().as_mut_ptr();
+        // SAFETY: `ptr` comes from a mutable reference, so it's valid and aligned.
+        // Moreover we never expose the lifetime of the `Uninit` created, so `from_ptr`
+        // is safe to call.
+        let uninit = unsafe { Uninit::from_ptr(ptr) };
+        let init = f(uninit, addr);
+        mem::forget(init);
+        // SAFETY: The existance of `init` ensures the value has been initialized.
+        unsafe { arc.assume_init() }
+    }
+
+    fn pin_with<F>(f: F) -> Pin<Self>
+    where
+        F: FnOnce(PinUninit<'_, T>) -> Pin<Init<'_, T>>,
+    {
+        // If `f` panics the memory is leaked, since it may contain pinned fields that
+        // were never dropped.
+        let mut arc = ManuallyDrop::new(Arc::<T>::new_uninit());
+        // The `Arc` was just created, so it's unique and `get_mut` can't fail.
+        let ptr = Arc::get_mut(&mut *arc).unwrap().as_mut_ptr();
+        // SAFETY: `ptr` comes from a mutable reference, so it's valid and aligned. Moreover
+        // we never expose the lifetime of the `Uninit` created, so `from_ptr` is safe to call.
+        let uninit = unsafe { Uninit::from_ptr(ptr) };
+        // SAFETY: The memory will be pinned, i.e. not reused until the value is dropped,
+        // once initialized.
+        let init = unsafe { uninit.pin_init_with(f) };
+        mem::forget(init);
+        // SAFETY: The existance of `init` ensures the value has been initialized.
+        let arc = unsafe { ManuallyDrop::into_inner(arc).assume_init() };
+        // SAFETY: The value is never moved out of the allocation of an `Arc`, and it's
+        // dropped before the allocation is freed.
+        unsafe { Pin::new_unchecked(arc) }
+    }
+}
+
+/// Initializes the value of `arc`, which must be unique, with the constructor `f`.
+fn init_arc<T, F>(mut arc: Arc<MaybeUninit<T>>, f: F) -> Arc<T>
+where
+    F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+{
+    // The `Arc` is unique, so `get_mut` can't fail.
+    let ptr = Arc::get_mut(&mut arc).unwrap().as_mut_ptr();
+    // SAFETY: `ptr` comes from a mutable reference, so it's valid and aligned.
+    // Moreover we never expose the lifetime of the `Uninit` created, so `from_ptr`
+    // is safe to call.
+    let uninit = unsafe { Uninit::from_ptr(ptr) };
+    let init = f(uninit);
+    mem::forget(init);
+    // SAFETY: The existance of `init` ensures the value has been initialized.
+    unsafe { arc.assume_init() }
+}
+
+/// Extension trait for constructing an [`Arc`] of a slice in place.
+pub trait ArcSliceExt<T> {
+    /// Allocates a slice of `len` elements on the heap and initializes it with
+    /// the given constructor `f`, for example [`slice_each`].
+    ///
+    /// If `f` panics the memory is deallocated.
+    ///
+    /// Unlike [`BoxSliceExt`] there's no fallible version of this method, not even
+    /// with the `nightly` feature, since the standard library provides no way to handle
+    /// allocation failures of an `Arc<[T]>`.
+    ///
+    /// [`slice_each`]: crate::slice_each
+    /// [`BoxSliceExt`]: crate::BoxSliceExt
+    fn new_slice_with<F>(len: usize, f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, [T]>) -> Init<'_, [T]>;
+}
+
+impl<T> ArcSliceExt<T> for Arc<[T]> {
+    fn new_slice_with<F>(len: usize, f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, [T]>) -> Init<'_, [T]>,
+    {
+        let mut arc = Arc::<[T]>::new_uninit_slice(len);
+        // The `Arc` was just created, so it's unique and `get_mut` can't fail.
+        let slice = Arc::get_mut(&mut arc).unwrap();
+        // Can't use `cast` because it requires `U` to be `Sized`.
+        let ptr = slice as *mut [MaybeUninit<T>] as *mut [T];
+        // SAFETY: `ptr` comes from a mutable reference, so it's valid and aligned.
+        // Moreover we never expose the lifetime of the `Uninit` created, so `from_ptr`
+        // is safe to call.
+        let uninit = unsafe { Uninit::from_ptr(ptr) };
+        let init = f(uninit);
+        mem::forget(init);
+        // SAFETY: The existance of `init` ensures the elements have been initialized.
+        unsafe { arc.assume_init() }
+    }
+}
+
+/// Extension trait for constructing an [`Arc`] with a custom allocator in place.
+#[cfg(feature = "nightly")]
+pub trait ArcAllocExt<T, A: Allocator> {
+    /// Allocates some memory with `alloc` and initializes it with the given
+    /// constructor `f`, without the value ever being on the stack.
+    ///
+    /// If `f` panics the memory is deallocated.
+    fn new_in_with<F>(alloc: A, f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>;
+}
+
+#[cfg(feature = "nightly")]
+impl<T, A: Allocator> ArcAllocExt<T, A> for Arc<T, A> {
+    fn new_in_with<F>(alloc: A, f: F) -> Self
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+    {
+        let mut arc = Arc::<T, A>::new_uninit_in(alloc);
+        // The `Arc` was just created, so it's unique and `get_mut` can't fail.
+        let ptr = Arc::get_mut(&mut arc).unwrap().as_mut_ptr();
+        // SAFETY: `ptr` comes from a mutable reference, so it's valid and aligned.
+        // Moreover we never expose the lifetime of the `Uninit` created, so `from_ptr`
+        // is safe to call.
+        let uninit = unsafe { Uninit::from_ptr(ptr) };
+        let init = f(uninit);
+        mem::forget(init);
+        // SAFETY: The existance of `init` ensures the value has been initialized.
+        unsafe { arc.assume_init() }
+    }
+}
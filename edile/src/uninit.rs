@@ -1,8 +1,9 @@
-use crate::{Init, Invariant};
+use crate::{Init, Invariant, PinUninit};
 
 use core::any::type_name;
 use core::fmt;
 use core::mem::MaybeUninit;
+use core::pin::Pin;
 use core::ptr::NonNull;
 
 /// Represents some unitialized place that needs to be initialized.
@@ -47,6 +48,44 @@ impl<'a, T: ?Sized> Uninit<'a, T> {
         f(self)
     }
 
+    /// Initializes `self` with the given fallible constructor `f`.
+    ///
+    /// If `f` returns an error `self` is consumed without being initialized, so
+    /// the place it pointed to can be initialized again.
+    ///
+    /// See also [`try_ctor!`] for naming the type of fallible constructors.
+    ///
+    /// [`try_ctor!`]: crate::try_ctor
+    pub fn try_init_with<F, E>(self, f: F) -> Result<Init<'a, T>, E>
+    where
+        F: FnOnce(Uninit<'_, T>) -> Result<Init<'_, T>, E>,
+    {
+        f(self)
+    }
+
+    /// Initializes `self` with the given pinned constructor `f`, which can rely on
+    /// the value staying at its address.
+    ///
+    /// The result is a `Pin<Init<'a, T>>`, which can't be returned by a [`ctor!`], so
+    /// this is meant for containers that own the memory of `self` and pin the value
+    /// once initialized, like [`AllocBox::pin_in_with`]. See also [`pin_ctor!`] for naming
+    /// the type of pinned constructors.
+    ///
+    /// # Safety
+    /// Same as [`into_pin`].
+    ///
+    /// [`into_pin`]: Uninit::into_pin
+    /// [`ctor!`]: crate::ctor
+    /// [`pin_ctor!`]: crate::pin_ctor
+    /// [`AllocBox::pin_in_with`]: crate::AllocBox::pin_in_with
+    pub unsafe fn pin_init_with<F>(self, f: F) -> Pin<Init<'a, T>>
+    where
+        F: FnOnce(PinUninit<'_, T>) -> Pin<Init<'_, T>>,
+    {
+        // SAFETY: The caller upholds the requirements of `into_pin`.
+        unsafe { self.into_pin() }.init_with(f)
+    }
+
     /// Assumes `self` has been initialized, returning an [`Init<T>`] token that guarantees
     /// this place has been initialized.
     ///
@@ -79,10 +118,32 @@ impl<'a, T: ?Sized> Uninit<'a, T> {
         }
     }
 
+    /// Converts an `Uninit<'a, T>` into a `PinUninit<'a, T>`.
+    ///
+    /// Note that initializing a `PinUninit<'a, T>` results in a `Pin<Init<'a, T>>`
+    /// which can't be converted back into an `Init<'a, T>`, so this is mostly useful
+    /// when `self` is part of the projection of an `Uninit` that will be pinned.
+    ///
+    /// # Safety
+    /// The memory pointed by `self` must not be reused or invalidated before the value
+    /// is dropped once initialized, see [`PinUninit::from_ptr`]. This doesn't hold in
+    /// general, e.g. for the memory of a `MaybeUninit` on the stack.
+    pub unsafe fn into_pin(self) -> PinUninit<'a, T> {
+        // SAFETY: `self` already upholds the requirements of `Uninit::from_ptr`, and
+        // the caller ensures the memory won't be reused before the value is dropped.
+        unsafe { PinUninit::from_ptr(self.data.as_ptr()) }
+    }
+
     /// Returns a pointer to the data pointed by `self`
     pub fn as_mut_ptr(&mut self) -> *mut T {
         self.data.as_ptr()
     }
+
+    /// Returns a pointer to the data pointed by `self` without requiring
+    /// exclusive access.
+    pub(crate) fn as_non_null(&self) -> NonNull<T> {
+        self.data
+    }
 }
 
 impl<'a, T> Uninit<'a, [T]> {
@@ -106,7 +167,7 @@ impl<'a, T> Uninit<'a, [T]> {
         let ptr = self.data.as_ptr() as *const [MaybeUninit<T>];
         // SAFETY: `self.as_mut_ptr()` returns a valid pointer and `MaybeUninit` doesn't
         // need to be initialized.
-        unsafe { (*ptr).len() }
+        unsafe { (&*ptr).len() }
     }
 }
 
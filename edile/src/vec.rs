@@ -0,0 +1,139 @@
+use crate::{slice_each, Init, Uninit};
+
+use alloc::vec::Vec;
+use core::mem::{self, MaybeUninit};
+use core::ptr;
+
+/// Extension trait for constructing the elements of a [`Vec`] in place.
+pub trait VecExt<T> {
+    /// Appends an element to the back of the vector, initializing it in place
+    /// with the given constructor `f`.
+    ///
+    /// If `f` panics the vector is left unchanged, except for its capacity.
+    fn push_with<F>(&mut self, f: F)
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>;
+
+    /// Inserts an element at position `index`, shifting all the elements after it
+    /// to the right, and initializes it in place with the given constructor `f`.
+    ///
+    /// If `f` panics the elements are shifted back and the vector is left unchanged,
+    /// except for its capacity.
+    ///
+    /// # Panics
+    /// Panics if `index > len`.
+    fn insert_with<F>(&mut self, index: usize, f: F)
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>;
+
+    /// Appends `n` elements to the back of the vector, initializing each of them
+    /// in place by calling `f` with its index, starting from 0, and its [`Uninit<T>`].
+    ///
+    /// If `f` panics the elements it already initialized are dropped and the vector
+    /// is left unchanged, except for its capacity.
+    fn extend_with<F>(&mut self, n: usize, f: F)
+    where
+        F: FnMut(usize, Uninit<'_, T>) -> Init<'_, T>;
+}
+
+impl<T> VecExt<T> for Vec<T> {
+    fn push_with<F>(&mut self, f: F)
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+    {
+        self.reserve(1);
+        let ptr = self.spare_capacity_mut()[0].as_mut_ptr();
+        // SAFETY: `ptr` points to the spare capacity of the vector, so it's valid and aligned.
+        // Moreover we never expose the lifetime of the `Uninit` created, so `from_ptr`
+        // is safe to call.
+        let uninit = unsafe { Uninit::from_ptr(ptr) };
+        let init = f(uninit);
+        mem::forget(init);
+        // SAFETY: The existance of `init` ensures the element after the last one has been
+        // initialized, and we reserved the capacity for it.
+        unsafe { self.set_len(self.len() + 1) };
+    }
+
+    fn insert_with<F>(&mut self, index: usize, f: F)
+    where
+        F: FnOnce(Uninit<'_, T>) -> Init<'_, T>,
+    {
+        // Invariant: the elements after `index` have been shifted one position to the right,
+        // and the length of `vec` has been set to `index`.
+        struct ShiftBackOnPanic<'v, T> {
+            vec: &'v mut Vec<T>,
+            index: usize,
+            len: usize,
+        }
+
+        impl<T> Drop for ShiftBackOnPanic<'_, T> {
+            fn drop(&mut self) {
+                let ptr = self.vec.as_mut_ptr();
+                // SAFETY: for `ShiftBackOnPanic`'s invariant the elements are shifted right,
+                // so moving them back restores the original vector.
+                unsafe {
+                    let hole = ptr.add(self.index);
+                    ptr::copy(hole.add(1), hole, self.len - self.index);
+                    self.vec.set_len(self.len);
+                }
+            }
+        }
+
+        let len = self.len();
+        assert!(
+            index <= len,
+            "insertion index (is {}) should be <= len (is {})",
+            index,
+            len
+        );
+        self.reserve(1);
+
+        // SAFETY: `index <= len` and we reserved space for one more element, so the elements
+        // after `index` can be shifted to the right. The length is set to `index` so that
+        // the shifted elements won't be dropped twice.
+        let hole = unsafe {
+            let hole = self.as_mut_ptr().add(index);
+            ptr::copy(hole, hole.add(1), len - index);
+            self.set_len(index);
+            hole
+        };
+        let guard = ShiftBackOnPanic {
+            vec: self,
+            index,
+            len,
+        };
+
+        // SAFETY: `hole` is in bounds of the allocation, so it's valid and aligned, and its
+        // element has been moved. Moreover we never expose the lifetime of the `Uninit`
+        // created, so `from_ptr` is safe to call.
+        let uninit = unsafe { Uninit::from_ptr(hole) };
+        let init = f(uninit);
+        mem::forget(init);
+
+        // Now that the hole has been filled don't shift the elements back.
+        mem::forget(guard);
+
+        // SAFETY: The existance of `init` ensures the hole has been initialized,
+        // so the first `len + 1` elements are initialized.
+        unsafe { self.set_len(len + 1) };
+    }
+
+    fn extend_with<F>(&mut self, n: usize, f: F)
+    where
+        F: FnMut(usize, Uninit<'_, T>) -> Init<'_, T>,
+    {
+        self.reserve(n);
+        let spare = &mut self.spare_capacity_mut()[..n];
+        // Can't use `cast` because it requires `U` to be `Sized`.
+        let ptr = spare as *mut [MaybeUninit<T>] as *mut [T];
+        // SAFETY: `ptr` comes from a mutable reference, so it's valid and aligned.
+        // Moreover we never expose the lifetime of the `Uninit` created, so `from_ptr`
+        // is safe to call.
+        let uninit = unsafe { Uninit::from_ptr(ptr) };
+        let init = uninit.init_with(slice_each(f));
+        mem::forget(init);
+        // SAFETY: The existance of `init` ensures the `n` elements after the last one have
+        // been initialized, and we reserved the capacity for them.
+        unsafe { self.set_len(self.len() + n) };
+    }
+}
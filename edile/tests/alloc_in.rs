@@ -0,0 +1,54 @@
+#![cfg(feature = "nightly")]
+#![feature(allocator_api)]
+// Newer toolchains moved part of `allocator_api` to `allocator_ext`.
+#![allow(stable_features)]
+
+use edile::{ArcAllocExt, BoxAllocExt, RcAllocExt};
+
+use std::alloc::{AllocError, Allocator, Global, Layout};
+use std::cell::Cell;
+use std::ptr::NonNull;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// An allocator counting its live allocations.
+struct Counting<'a>(&'a Cell<isize>);
+
+unsafe impl Allocator for Counting<'_> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.set(self.0.get() + 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.0.set(self.0.get() - 1);
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+#[test]
+fn box_new_in_with() {
+    let live = Cell::new(0);
+    let boxed = Box::new_in_with(Counting(&live), |uninit| uninit.init([7u32; 16]));
+    assert_eq!((*boxed, live.get()), ([7; 16], 1));
+    drop(boxed);
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn rc_new_in_with() {
+    let live = Cell::new(0);
+    let rc = Rc::new_in_with(Counting(&live), |uninit| uninit.init(String::from("rc")));
+    assert_eq!((&**rc, live.get()), ("rc", 1));
+    drop(rc);
+    assert_eq!(live.get(), 0);
+}
+
+#[test]
+fn arc_new_in_with() {
+    let live = Cell::new(0);
+    let arc = Arc::new_in_with(Counting(&live), |uninit| uninit.init(String::from("arc")));
+    assert_eq!((&**arc, live.get()), ("arc", 1));
+    drop(arc);
+    assert_eq!(live.get(), 0);
+}
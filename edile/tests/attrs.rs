@@ -0,0 +1,129 @@
+use edile::{proj_fn, try_proj_fn, Init, Own};
+use edile_derive::ProjConstruct;
+
+use std::cell::Cell;
+use std::mem::MaybeUninit;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+thread_local! {
+    static POST_INITS: Cell<usize> = const { Cell::new(0) };
+    static DROPS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Counts its drops in `DROPS`, so that it can be created by a default expression.
+struct Tracked;
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        DROPS.with(|drops| drops.set(drops.get() + 1));
+    }
+}
+
+#[derive(ProjConstruct)]
+#[edile(post_init = Counter::finish)]
+struct Counter {
+    name: String,
+    #[edile(default = 10)]
+    start: u32,
+    #[edile(default)]
+    count: u32,
+    #[edile(default = "default".len())]
+    len: usize,
+}
+
+impl Counter {
+    fn finish(this: &mut Init<'_, Self>) {
+        POST_INITS.with(|calls| calls.set(calls.get() + 1));
+        this.count = this.start + this.len as u32;
+    }
+}
+
+#[test]
+fn defaults_and_post_init_are_applied() {
+    let before = POST_INITS.with(Cell::get);
+    let mut storage = MaybeUninit::uninit();
+    let counter = Own::new_with(
+        &mut storage,
+        proj_fn::<Counter, _>(|_, proj| CounterInitProj {
+            name: proj.name.init("counter".into()),
+        }),
+    );
+    assert_eq!(POST_INITS.with(Cell::get), before + 1);
+    assert_eq!(counter.name, "counter");
+    assert_eq!((counter.start, counter.len), (10, 7));
+    // Set by `post_init`, which saw the defaults.
+    assert_eq!(counter.count, 17);
+}
+
+#[test]
+fn post_init_is_not_called_on_error() {
+    let before = POST_INITS.with(Cell::get);
+    let mut storage = MaybeUninit::uninit();
+    let res = Own::try_new_with(
+        &mut storage,
+        try_proj_fn::<Counter, _, _>(|_, _| Err("failed")),
+    );
+    assert_eq!(res.err(), Some("failed"));
+    assert_eq!(POST_INITS.with(Cell::get), before);
+}
+
+fn fail() -> u8 {
+    panic!("default failed")
+}
+
+#[derive(ProjConstruct)]
+struct PanickingDefault {
+    first: Tracked,
+    #[edile(default = Tracked)]
+    second: Tracked,
+    #[edile(default = fail())]
+    _third: u8,
+    fourth: Tracked,
+}
+
+#[test]
+fn panic_in_default_drops_initialized_fields() {
+    let before = DROPS.with(Cell::get);
+    let mut storage = MaybeUninit::uninit();
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        Own::new_with(
+            &mut storage,
+            proj_fn::<PanickingDefault, _>(|_, proj| PanickingDefaultInitProj {
+                first: proj.first.init(Tracked),
+                fourth: proj.fourth.init(Tracked),
+            }),
+        );
+    }));
+    assert!(res.is_err());
+    // `first` and `fourth` from the projection and `second` from its default.
+    assert_eq!(DROPS.with(Cell::get), before + 3);
+}
+
+#[derive(ProjConstruct)]
+#[edile(post_init = panic_after_init)]
+struct PanickingPostInit {
+    _first: Tracked,
+    #[edile(default)]
+    second: Option<Tracked>,
+}
+
+fn panic_after_init(this: &mut Init<'_, PanickingPostInit>) {
+    this.second = Some(Tracked);
+    panic!("post_init failed")
+}
+
+#[test]
+fn panic_in_post_init_drops_value() {
+    let before = DROPS.with(Cell::get);
+    let mut storage = MaybeUninit::uninit();
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        Own::new_with(
+            &mut storage,
+            proj_fn::<PanickingPostInit, _>(|_, proj| PanickingPostInitInitProj {
+                _first: proj._first.init(Tracked),
+            }),
+        );
+    }));
+    assert!(res.is_err());
+    assert_eq!(DROPS.with(Cell::get), before + 2);
+}
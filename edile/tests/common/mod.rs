@@ -0,0 +1,60 @@
+//! Fixtures shared by the integration tests.
+//!
+//! Each test crate only uses some of them.
+#![allow(dead_code)]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::panic::{self, catch_unwind, AssertUnwindSafe};
+use std::sync::Once;
+
+/// A global allocator counting the live allocations of at least `BIG` bytes
+/// made by the current thread.
+///
+/// The tests using it must register it with `#[global_allocator]`.
+pub struct CountingGlobal;
+
+pub const BIG: usize = 4096;
+
+thread_local! {
+    pub static LIVE: Cell<isize> = const { Cell::new(0) };
+}
+
+fn track(layout: Layout, delta: isize) {
+    if layout.size() >= BIG {
+        let _ = LIVE.try_with(|live| live.set(live.get() + delta));
+    }
+}
+
+unsafe impl GlobalAlloc for CountingGlobal {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        track(layout, 1);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        track(layout, -1);
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+/// Runs `f`, which must panic, and returns how many allocations tracked by
+/// `CountingGlobal` it leaked.
+pub fn leaked_by_panic(f: impl FnOnce()) -> isize {
+    // The default hook may allocate big buffers, e.g. to print a backtrace.
+    static SILENCE_PANICS: Once = Once::new();
+    SILENCE_PANICS.call_once(|| panic::set_hook(Box::new(|_| {})));
+
+    let live = LIVE.with(Cell::get);
+    assert!(catch_unwind(AssertUnwindSafe(f)).is_err());
+    LIVE.with(Cell::get) - live
+}
+
+/// Increments the counter it borrows when dropped.
+pub struct DropCounter<'a>(pub &'a Cell<usize>);
+
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
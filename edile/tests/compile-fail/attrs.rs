@@ -0,0 +1,27 @@
+use edile_derive::{PinProjConstruct, ProjConstruct};
+
+#[derive(ProjConstruct)]
+struct Unknown {
+    #[edile(skip)]
+    a: u8,
+}
+
+#[derive(ProjConstruct)]
+#[edile(default)]
+struct DefaultOnType {
+    a: u8,
+}
+
+#[derive(ProjConstruct)]
+#[repr(u8)]
+enum DefaultInVariant {
+    A(#[edile(default)] u8),
+}
+
+#[derive(PinProjConstruct)]
+struct PinArgs {
+    #[pin(always)]
+    a: u8,
+}
+
+fn main() {}
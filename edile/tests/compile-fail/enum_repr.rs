@@ -0,0 +1,19 @@
+use edile_derive::ProjConstruct;
+
+#[derive(ProjConstruct)]
+enum NoRepr {
+    A(u8),
+    B,
+}
+
+#[derive(ProjConstruct)]
+#[repr(C)]
+enum ReprC {
+    A(u8),
+    B,
+}
+
+#[derive(ProjConstruct)]
+enum Empty {}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use edile_derive::ProjMove;
+
+#[derive(ProjMove)]
+struct Guard {
+    name: String,
+}
+
+impl Drop for Guard {
+    fn drop(&mut self) {}
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use edile_derive::ProjMove;
+
+#[derive(ProjMove)]
+enum Either {
+    Left(u8),
+    Right(u32),
+}
+
+fn main() {}
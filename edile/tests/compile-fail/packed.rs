@@ -0,0 +1,10 @@
+use edile_derive::ProjConstruct;
+
+#[derive(ProjConstruct)]
+#[repr(C, packed)]
+struct Packed {
+    a: u8,
+    b: u32,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use edile_derive::ProjMove;
+
+#[derive(ProjMove)]
+#[repr(packed)]
+struct Packed {
+    a: u8,
+    b: u32,
+}
+
+fn main() {}
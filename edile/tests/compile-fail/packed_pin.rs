@@ -0,0 +1,11 @@
+use edile_derive::PinProjConstruct;
+
+#[derive(PinProjConstruct)]
+#[repr(packed(2))]
+struct Packed {
+    #[pin]
+    a: u8,
+    b: u32,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use edile::{AllocBox, Global};
+
+fn main() {
+    let alloc = Global;
+    let pinned = AllocBox::pin_in_with(&alloc, |uninit| uninit.init(1u32));
+    drop(pinned);
+
+    let boxed = AllocBox::new_in_with(&alloc, |uninit| uninit.init(2u32));
+    let pinned = AllocBox::into_pin(boxed);
+    drop(pinned);
+}
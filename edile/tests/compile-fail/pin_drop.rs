@@ -0,0 +1,40 @@
+use edile::{PinnedDrop, PinnedDropToken};
+use edile_derive::PinProjConstruct;
+
+use std::pin::Pin;
+
+#[derive(PinProjConstruct)]
+struct WithDrop {
+    #[pin]
+    field: String,
+}
+
+impl Drop for WithDrop {
+    fn drop(&mut self) {}
+}
+
+#[derive(PinProjConstruct)]
+#[pin(Drop)]
+struct WrongArg {
+    #[pin]
+    field: String,
+}
+
+#[derive(PinProjConstruct)]
+#[pin(PinnedDrop)]
+struct MissingImpl {
+    #[pin]
+    field: String,
+}
+
+#[derive(PinProjConstruct)]
+struct MissingAttr {
+    #[pin]
+    field: String,
+}
+
+impl PinnedDrop for MissingAttr {
+    fn drop(self: Pin<&mut Self>, _: PinnedDropToken) {}
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use edile_derive::PinProjConstruct;
+
+#[derive(PinProjConstruct)]
+enum Either {
+    Left(#[pin] u8),
+    Right(u32),
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use edile::{PinnedDrop, PinnedDropToken};
+use edile_derive::PinProjConstruct;
+
+use std::pin::Pin;
+
+#[derive(PinProjConstruct)]
+#[pin(PinnedDrop)]
+struct ExplicitCall {
+    #[pin]
+    field: String,
+}
+
+impl PinnedDrop for ExplicitCall {
+    fn drop(self: Pin<&mut Self>, _: PinnedDropToken) {}
+}
+
+fn call_explicitly(this: Pin<&mut ExplicitCall>) {
+    PinnedDrop::drop(this, PinnedDropToken { _private: () });
+}
+
+fn main() {}
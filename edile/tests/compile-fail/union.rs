@@ -0,0 +1,21 @@
+use edile_derive::{PinProjConstruct, ProjConstruct, ProjMove};
+
+#[derive(ProjConstruct)]
+union Construct {
+    a: u8,
+    b: u32,
+}
+
+#[derive(PinProjConstruct)]
+union Pinned {
+    a: u8,
+    b: u32,
+}
+
+#[derive(ProjMove)]
+union Move {
+    a: u8,
+    b: u32,
+}
+
+fn main() {}
@@ -0,0 +1,75 @@
+use edile::{ctor, proj_fn, Own};
+use edile_derive::{Ctor, ProjConstruct};
+
+use std::mem::MaybeUninit;
+
+#[derive(ProjConstruct, Ctor)]
+struct Inner {
+    proj: u32,
+    name: String,
+}
+
+#[derive(ProjConstruct, Ctor)]
+struct Outer(Inner, u8);
+
+// The variants are only constructed in place, which the compiler can't see.
+#[allow(dead_code)]
+#[derive(ProjConstruct, Ctor)]
+#[repr(u8)]
+enum Shape {
+    Point,
+    Circle { proj: u32 },
+}
+
+fn value<T>(value: T) -> ctor!(T) {
+    move |uninit| uninit.init(value)
+}
+
+#[test]
+fn new_ctor_with_own() {
+    let mut storage = MaybeUninit::uninit();
+    let inner = Own::new_with(&mut storage, Inner::new_ctor(value(1), value("a".into())));
+    assert_eq!((inner.proj, &*inner.name), (1, "a"));
+
+    let mut storage = MaybeUninit::uninit();
+    let inner = Own::new_with(&mut storage, Inner::new_value_ctor(2, "b".into()));
+    assert_eq!((inner.proj, &*inner.name), (2, "b"));
+}
+
+#[test]
+fn new_ctor_with_init_with() {
+    let mut storage = MaybeUninit::uninit();
+    let outer = Own::new_with(
+        &mut storage,
+        proj_fn::<Outer, _>(|_, proj| {
+            OuterInitProj(
+                proj.0
+                    .init_with(Inner::new_ctor(value(3), value("c".into()))),
+                proj.1.init(4),
+            )
+        }),
+    );
+    assert_eq!((outer.0.proj, &*outer.0.name, outer.1), (3, "c", 4));
+
+    let mut storage = MaybeUninit::uninit();
+    let outer = Own::new_with(
+        &mut storage,
+        Outer::new_ctor(Inner::new_value_ctor(5, "d".into()), value(6)),
+    );
+    assert_eq!((outer.0.proj, &*outer.0.name, outer.1), (5, "d", 6));
+}
+
+#[test]
+fn variant_ctors() {
+    let mut storage = MaybeUninit::uninit();
+    let circle = Own::new_with(&mut storage, Shape::variant_circle_value_ctor(7));
+    assert!(matches!(*circle, Shape::Circle { proj: 7 }));
+    drop(circle);
+
+    let point = Own::new_with(&mut storage, Shape::variant_point_ctor());
+    assert!(matches!(*point, Shape::Point));
+    drop(point);
+
+    let circle = Own::new_with(&mut storage, Shape::variant_circle_ctor(value(8)));
+    assert!(matches!(*circle, Shape::Circle { proj: 8 }));
+}
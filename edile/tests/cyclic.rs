@@ -0,0 +1,93 @@
+#![cfg(feature = "alloc")]
+
+use edile::{array_each, proj_fn, ArcExt, Init, RcExt, StableAddr, Uninit};
+
+use std::cell::Cell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+use std::rc::Rc;
+use std::sync::Arc;
+
+mod common;
+
+use common::DropCounter;
+
+struct Node {
+    name: String,
+    this: StableAddr<Node>,
+}
+
+fn failing<T>(_: Uninit<'_, T>) -> Init<'_, T> {
+    panic!("construction failed")
+}
+
+#[test]
+fn rc_receives_final_address() {
+    let rc = Rc::new_cyclic_with(|uninit, addr| {
+        uninit.init(Node {
+            name: "rc".into(),
+            this: addr,
+        })
+    });
+    assert_eq!(rc.name, "rc");
+    assert!(ptr::eq(Rc::as_ptr(&rc), rc.this.as_ptr()));
+    assert!(ptr::eq(Rc::downgrade(&rc).as_ptr(), rc.this.as_ptr()));
+}
+
+#[test]
+fn arc_receives_final_address() {
+    let arc = Arc::new_cyclic_with(|uninit, addr| {
+        uninit.init(Node {
+            name: "arc".into(),
+            this: addr,
+        })
+    });
+    assert_eq!(arc.name, "arc");
+    assert!(ptr::eq(Arc::as_ptr(&arc), arc.this.as_ptr()));
+    assert!(ptr::eq(Arc::downgrade(&arc).as_ptr(), arc.this.as_ptr()));
+}
+
+#[test]
+fn big_values_are_built_in_place() {
+    // Way bigger than the stack of the test threads.
+    const LEN: usize = 64 << 20;
+
+    let rc = Rc::<[u8; LEN]>::new_cyclic_with(|uninit, _| {
+        uninit.init_with(array_each(|idx, uninit| uninit.init(idx as u8)))
+    });
+    assert_eq!((rc[0], rc[LEN - 1]), (0, u8::MAX));
+    drop(rc);
+
+    let arc = Arc::<[u8; LEN]>::new_cyclic_with(|uninit, _| {
+        uninit.init_with(array_each(|idx, uninit| uninit.init(idx as u8)))
+    });
+    assert_eq!((arc[0], arc[LEN - 1]), (0, u8::MAX));
+}
+
+#[test]
+fn rc_panic_drops_initialized_fields() {
+    let drops = Cell::new(0);
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        Rc::new_cyclic_with(|uninit, _| {
+            uninit.init_with(proj_fn::<(DropCounter<'_>, DropCounter<'_>), _>(
+                |_, (first, second)| (first.init(DropCounter(&drops)), second.init_with(failing)),
+            ))
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn arc_panic_drops_initialized_fields() {
+    let drops = Cell::new(0);
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        Arc::new_cyclic_with(|uninit, _| {
+            uninit.init_with(proj_fn::<(DropCounter<'_>, DropCounter<'_>), _>(
+                |_, (first, second)| (first.init(DropCounter(&drops)), second.init_with(failing)),
+            ))
+        })
+    }));
+    assert!(result.is_err());
+    assert_eq!(drops.get(), 1);
+}
@@ -0,0 +1,74 @@
+use edile::{proj_fn, Own};
+use edile_derive::ProjConstruct;
+
+use std::mem::MaybeUninit;
+
+// The variants are only constructed in place, which the compiler can't see.
+#[allow(dead_code)]
+#[derive(ProjConstruct, Debug, PartialEq)]
+#[repr(u8)]
+enum Message {
+    Empty,
+    Packet { tag: u8, len: u32 },
+    Text(String, u16) = 10,
+    Last,
+}
+
+/// Reads the discriminant of a `#[repr(u8)]` enum, which is its first byte.
+fn discriminant(message: &Message) -> u8 {
+    // SAFETY: `Message` is `#[repr(u8)]`, so it starts with its `u8` discriminant.
+    unsafe { *(message as *const Message).cast::<u8>() }
+}
+
+#[test]
+fn unit_variants() {
+    let mut storage = MaybeUninit::uninit();
+    let empty = Own::new_with(
+        &mut storage,
+        proj_fn::<Message, _>(|_, proj| proj.variant_empty()),
+    );
+    assert_eq!(*empty, Message::Empty);
+    assert_eq!(discriminant(&empty), 0);
+    drop(empty);
+
+    let last = Own::new_with(
+        &mut storage,
+        proj_fn::<Message, _>(|_, proj| proj.variant_last()),
+    );
+    assert_eq!(*last, Message::Last);
+    assert_eq!(discriminant(&last), 11);
+}
+
+#[test]
+fn named_variant_with_tag_field() {
+    let mut storage = MaybeUninit::uninit();
+    let packet = Own::new_with(
+        &mut storage,
+        proj_fn::<Message, _>(|_, proj| {
+            proj.variant_packet(|fields| MessagePacketInitProj {
+                tag: fields.tag.init(7),
+                len: fields.len.init(1024),
+            })
+        }),
+    );
+    match *packet {
+        Message::Packet { tag, len } => assert_eq!((tag, len), (7, 1024)),
+        _ => panic!("wrong variant"),
+    }
+    assert_eq!(discriminant(&packet), 1);
+}
+
+#[test]
+fn tuple_variant_with_explicit_discriminant() {
+    let mut storage = MaybeUninit::uninit();
+    let text = Own::new_with(
+        &mut storage,
+        proj_fn::<Message, _>(|_, proj| {
+            proj.variant_text(|fields| {
+                MessageTextInitProj(fields.0.init("hi".into()), fields.1.init(3))
+            })
+        }),
+    );
+    assert_eq!(*text, Message::Text("hi".into(), 3));
+    assert_eq!(discriminant(&text), 10);
+}
@@ -0,0 +1,45 @@
+use edile::project::PinProjMut;
+use edile::{pin_proj_fn, proj_fn, Own};
+use edile_derive::{PinProjConstruct, ProjConstruct};
+
+use std::mem::MaybeUninit;
+
+#[derive(ProjConstruct, Debug, PartialEq)]
+struct Unit;
+
+#[derive(ProjConstruct, Debug, PartialEq)]
+struct Empty {}
+
+#[derive(ProjConstruct, Debug, PartialEq)]
+struct EmptyTuple();
+
+#[derive(PinProjConstruct, Debug, PartialEq)]
+struct PinUnit;
+
+#[test]
+fn unit_struct() {
+    let mut storage = MaybeUninit::uninit();
+    let unit = Own::new_with(&mut storage, proj_fn::<Unit, _>(|_, proj: ()| proj));
+    assert_eq!(*unit, Unit);
+}
+
+#[test]
+fn empty_structs() {
+    let mut storage = MaybeUninit::uninit();
+    let empty = Own::new_with(&mut storage, proj_fn::<Empty, _>(|_, ()| ()));
+    assert_eq!(*empty, Empty {});
+
+    let mut storage = MaybeUninit::uninit();
+    let empty = Own::new_with(&mut storage, proj_fn::<EmptyTuple, _>(|_, ()| ()));
+    assert_eq!(*empty, EmptyTuple());
+}
+
+#[test]
+fn pinned_unit_struct() {
+    let mut storage = MaybeUninit::uninit();
+    // SAFETY: `unit` is dropped at the end of the test, before `storage`.
+    let mut unit =
+        unsafe { Own::pin_new_with(&mut storage, pin_proj_fn::<PinUnit, _>(|_, ()| ())) };
+    let () = unit.as_mut().project_pin();
+    assert_eq!(*unit, PinUnit);
+}
@@ -0,0 +1,99 @@
+use edile::{proj_fn, try_proj_fn, Own};
+use edile_derive::{Ctor, ProjConstruct};
+
+use std::marker::PhantomData;
+use std::mem::MaybeUninit;
+
+trait Pod: Copy {}
+
+impl Pod for u16 {}
+
+#[derive(ProjConstruct, Ctor)]
+struct Buffer<'a, T: Pod, const N: usize> {
+    name: &'a str,
+    data: [T; N],
+}
+
+// The variants are only constructed in place, which the compiler can't see.
+#[allow(dead_code)]
+#[derive(ProjConstruct)]
+#[repr(u8)]
+enum Either<'x, T: 'x> {
+    Owned(T),
+    Borrowed(&'x u8),
+}
+
+#[derive(ProjConstruct)]
+struct Convert<T: Into<U>, U> {
+    value: T,
+    #[edile(default)]
+    _marker: PhantomData<U>,
+}
+
+#[derive(ProjConstruct)]
+struct Shadow<InitFn, InitErr>(InitFn, InitErr);
+
+#[test]
+fn lifetime_type_and_const_params() {
+    let name = String::from("samples");
+    let mut storage = MaybeUninit::uninit();
+    let buffer = Own::new_with(
+        &mut storage,
+        proj_fn::<Buffer<'_, u16, 3>, _>(|_, proj| BufferInitProj {
+            name: proj.name.init(&name),
+            data: proj.data.init([1, 2, 3]),
+        }),
+    );
+    assert_eq!((buffer.name, buffer.data), ("samples", [1, 2, 3]));
+    drop(buffer);
+
+    let buffer = Own::new_with(&mut storage, Buffer::new_value_ctor(&name[..3], [4, 5, 6]));
+    assert_eq!((buffer.name, buffer.data), ("sam", [4, 5, 6]));
+}
+
+#[test]
+fn variant_without_lifetime() {
+    let byte = 7;
+    let mut storage = MaybeUninit::uninit();
+    let owned = Own::new_with(
+        &mut storage,
+        proj_fn::<Either<'_, String>, _>(|_, proj| {
+            proj.variant_owned(|fields| EitherOwnedInitProj(fields.0.init("owned".into())))
+        }),
+    );
+    assert!(matches!(&*owned, Either::Owned(s) if s == "owned"));
+    drop(owned);
+
+    let borrowed = Own::new_with(
+        &mut storage,
+        proj_fn::<Either<'_, String>, _>(|_, proj| {
+            proj.variant_borrowed(|fields| EitherBorrowedInitProj(fields.0.init(&byte)))
+        }),
+    );
+    assert!(matches!(*borrowed, Either::Borrowed(&7)));
+}
+
+#[test]
+fn bound_on_param_of_default_field() {
+    let mut storage = MaybeUninit::uninit();
+    let convert = Own::new_with(
+        &mut storage,
+        proj_fn::<Convert<u8, u32>, _>(|_, proj| ConvertInitProj {
+            value: proj.value.init(9),
+        }),
+    );
+    assert_eq!(u32::from(convert.value), 9);
+}
+
+#[test]
+fn params_named_like_derive_generics() {
+    let mut storage = MaybeUninit::uninit();
+    let shadow = Own::try_new_with(
+        &mut storage,
+        try_proj_fn::<Shadow<u8, &str>, _, ()>(|_, proj| {
+            Ok(ShadowInitProj(proj.0.init(1), proj.1.init("two")))
+        }),
+    );
+    let shadow = shadow.unwrap();
+    assert_eq!((shadow.0, shadow.1), (1, "two"));
+}
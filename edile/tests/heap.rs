@@ -0,0 +1,111 @@
+#![cfg(feature = "alloc")]
+
+use edile::{array_each, proj_fn, ArcExt, BoxExt, Init, RcExt, Uninit};
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+mod common;
+
+use common::{leaked_by_panic, CountingGlobal, DropCounter, BIG, LIVE};
+
+#[global_allocator]
+static GLOBAL: CountingGlobal = CountingGlobal;
+
+/// Way bigger than the stack of the test threads.
+const HUGE: usize = 64 << 20;
+
+/// Big enough for its allocations to be tracked by `CountingGlobal`.
+type Tracked<'a> = (DropCounter<'a>, [u8; BIG], DropCounter<'a>);
+
+fn failing<T>(_: Uninit<'_, T>) -> Init<'_, T> {
+    panic!("construction failed")
+}
+
+/// Initializes the first two fields of a `Tracked` and then panics.
+macro_rules! halfway {
+    ($drops:expr) => {
+        proj_fn::<Tracked<'_>, _>(|_, (first, payload, last)| {
+            (
+                first.init(DropCounter($drops)),
+                payload.init([0; BIG]),
+                last.init_with(failing),
+            )
+        })
+    };
+}
+
+#[test]
+fn box_new_with() {
+    let boxed = Box::new_with(|uninit| uninit.init(String::from("box")));
+    assert_eq!(*boxed, "box");
+}
+
+#[test]
+fn rc_new_with() {
+    let rc = Rc::new_with(|uninit| uninit.init(String::from("rc")));
+    assert_eq!(*rc, "rc");
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn arc_new_with() {
+    let arc = Arc::new_with(|uninit| uninit.init(String::from("arc")));
+    assert_eq!(*arc, "arc");
+    assert_eq!(Arc::strong_count(&arc), 1);
+}
+
+#[test]
+fn big_values_are_built_in_place() {
+    let boxed = Box::<[u8; HUGE]>::new_with(array_each(|idx, uninit| uninit.init(idx as u8)));
+    assert_eq!((boxed[0], boxed[HUGE - 1]), (0, u8::MAX));
+    drop(boxed);
+
+    let rc = Rc::<[u8; HUGE]>::new_with(array_each(|idx, uninit| uninit.init(idx as u8)));
+    assert_eq!((rc[0], rc[HUGE - 1]), (0, u8::MAX));
+    drop(rc);
+
+    let arc = Arc::<[u8; HUGE]>::new_with(array_each(|idx, uninit| uninit.init(idx as u8)));
+    assert_eq!((arc[0], arc[HUGE - 1]), (0, u8::MAX));
+}
+
+#[test]
+fn box_new_with_panic_deallocates() {
+    let drops = Cell::new(0);
+    let leaked = leaked_by_panic(|| {
+        Box::new_with(halfway!(&drops));
+    });
+    assert_eq!((leaked, drops.get()), (0, 1));
+}
+
+#[test]
+fn rc_new_with_panic_deallocates() {
+    let drops = Cell::new(0);
+    let leaked = leaked_by_panic(|| {
+        Rc::new_with(halfway!(&drops));
+    });
+    assert_eq!((leaked, drops.get()), (0, 1));
+}
+
+#[test]
+fn arc_new_with_panic_deallocates() {
+    let drops = Cell::new(0);
+    let leaked = leaked_by_panic(|| {
+        Arc::new_with(halfway!(&drops));
+    });
+    assert_eq!((leaked, drops.get()), (0, 1));
+}
+
+#[test]
+fn new_with_frees_memory_when_dropped() {
+    let drops = Cell::new(0);
+    let live = LIVE.with(Cell::get);
+    let boxed =
+        Box::new_with(|uninit| uninit.init((DropCounter(&drops), [0; BIG], DropCounter(&drops))));
+    let rc =
+        Rc::new_with(|uninit| uninit.init((DropCounter(&drops), [0; BIG], DropCounter(&drops))));
+    assert_eq!(LIVE.with(Cell::get), live + 2);
+    drop((boxed, rc));
+    assert_eq!((LIVE.with(Cell::get), drops.get()), (live, 4));
+}
@@ -0,0 +1,44 @@
+use edile::{local, Own};
+use edile_derive::ProjConstruct;
+
+#[derive(ProjConstruct)]
+struct Point {
+    x: u32,
+    y: u32,
+}
+
+#[test]
+fn new_binding() {
+    local!(value = new(String::from("local")));
+    assert_eq!(*value, "local");
+    assert_eq!(Own::into_inner(value), "local");
+}
+
+#[test]
+fn with_binding() {
+    local!(mut value: Vec<u32> = with uninit => uninit.init(vec![1, 2]));
+    value.push(3);
+    assert_eq!(*value, [1, 2, 3]);
+}
+
+#[test]
+fn with_proj_binding() {
+    local!(point: Point = with_proj proj => PointInitProj {
+        x: proj.x.init(1),
+        y: proj.y.init(2),
+    });
+    assert_eq!((point.x, point.y), (1, 2));
+}
+
+#[test]
+fn multiple_bindings() {
+    let storage = "not the macro's storage";
+    local! {
+        a = new(1u8);
+        mut b: (u8, u8) = with_proj (x, y) => (x.init(*a), y.init(2));
+        c: String = with uninit => uninit.init(storage.to_string());
+    }
+    b.1 += *a;
+    assert_eq!(*b, (1, 3));
+    assert_eq!(*c, storage);
+}
@@ -0,0 +1,105 @@
+use edile::{local, Own};
+use edile_derive::ProjMove;
+
+use std::cell::Cell;
+
+mod common;
+
+use common::DropCounter;
+
+#[derive(ProjMove)]
+struct Pair<'a> {
+    name: String,
+    first: DropCounter<'a>,
+    second: DropCounter<'a>,
+}
+
+#[derive(ProjMove)]
+struct Unit;
+
+#[derive(ProjMove)]
+struct Empty {}
+
+#[test]
+fn fieldless_structs() {
+    local! {
+        unit = new(Unit);
+        empty = new(Empty {});
+    }
+    let () = Own::into_proj(unit);
+    let () = Own::into_proj(empty);
+}
+
+#[test]
+fn struct_move_out_one_field() {
+    let drops = Cell::new(0);
+    local!(
+        pair = new(Pair {
+            name: "pair".into(),
+            first: DropCounter(&drops),
+            second: DropCounter(&drops),
+        })
+    );
+    let proj = Own::into_proj(pair);
+    let first = Own::into_inner(proj.first);
+    assert_eq!(*proj.name, "pair");
+    assert_eq!(drops.get(), 0);
+
+    drop(proj.second);
+    assert_eq!(drops.get(), 1);
+    drop(proj.name);
+    drop(first);
+    assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn struct_drop_whole_projection() {
+    let drops = Cell::new(0);
+    local!(
+        pair = new(Pair {
+            name: "pair".into(),
+            first: DropCounter(&drops),
+            second: DropCounter(&drops),
+        })
+    );
+    let name = Own::into_inner(Own::into_proj(pair).name);
+    assert_eq!(name, "pair");
+    assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn tuple_move_out_one_field() {
+    let drops = Cell::new(0);
+    local!(
+        tuple = new((
+            DropCounter(&drops),
+            DropCounter(&drops),
+            DropCounter(&drops)
+        ))
+    );
+    let (first, second, third) = Own::into_proj(tuple);
+    let second = Own::into_inner(second);
+    drop((first, third));
+    assert_eq!(drops.get(), 2);
+    drop(second);
+    assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn array_move_out_one_element() {
+    let drops = Cell::new(0);
+    local!(
+        array = new([
+            DropCounter(&drops),
+            DropCounter(&drops),
+            DropCounter(&drops),
+            DropCounter(&drops),
+        ])
+    );
+    let [first, rest @ ..] = Own::into_proj(array);
+    let first = Own::into_inner(first);
+    drop(rest);
+    assert_eq!(drops.get(), 3);
+    drop(first);
+    assert_eq!(drops.get(), 4);
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "alloc")]
+
+use edile::{pin_ctor, BoxExt, Init, Own, PinUninit, StableAddr, Uninit};
+
+use std::cell::Cell;
+use std::marker::PhantomPinned;
+use std::mem::{self, MaybeUninit};
+use std::pin::Pin;
+use std::ptr;
+
+/// A value that stores its own address and counts its drops.
+struct SelfRef<'a> {
+    value: u32,
+    this: StableAddr<SelfRef<'a>>,
+    drops: &'a Cell<usize>,
+    _pin: PhantomPinned,
+}
+
+impl Drop for SelfRef<'_> {
+    fn drop(&mut self) {
+        assert!(ptr::eq(self.this.as_ptr(), self));
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+fn self_ref<'a>(value: u32, drops: &'a Cell<usize>) -> pin_ctor!(SelfRef<'a>) {
+    move |uninit| {
+        let this = uninit.addr();
+        uninit.init(SelfRef {
+            value,
+            this,
+            drops,
+            _pin: PhantomPinned,
+        })
+    }
+}
+
+#[test]
+fn box_pin_with_pin_ctor() {
+    let drops = Cell::new(0);
+    let boxed = Box::pin_with(self_ref(1, &drops));
+    assert_eq!(boxed.value, 1);
+    assert!(ptr::eq(boxed.this.as_ptr(), &*boxed));
+    drop(boxed);
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn own_pin_new_with() {
+    let drops = Cell::new(0);
+    let mut storage = MaybeUninit::uninit();
+    let addr = storage.as_ptr();
+    // SAFETY: `own` is dropped below, before `storage` is reused.
+    let own = unsafe { Own::pin_new_with(&mut storage, self_ref(2, &drops)) };
+    assert_eq!(own.value, 2);
+    assert!(ptr::eq(own.this.as_ptr(), addr));
+    drop(own);
+    assert_eq!(drops.get(), 1);
+
+    // The storage can be reused once the pinned value has been dropped.
+    // SAFETY: Same as above.
+    let own = unsafe { Own::pin_new_with(&mut storage, self_ref(3, &drops)) };
+    assert_eq!(own.value, 3);
+    assert!(ptr::eq(own.this.as_ptr(), addr));
+    drop(own);
+    assert_eq!(drops.get(), 2);
+}
+
+/// A minimal pinned container that never frees its memory, which is
+/// what `Uninit::pin_init_with` is meant for.
+fn leak_pinned<'a, T, F>(f: F) -> &'a T
+where
+    T: 'a,
+    F: FnOnce(PinUninit<'_, T>) -> Pin<Init<'_, T>>,
+{
+    let slot: &'a mut MaybeUninit<T> = Box::leak(Box::new(MaybeUninit::uninit()));
+    let ptr = slot.as_mut_ptr();
+    // SAFETY: `ptr` comes from a reference, so it's valid and aligned, and the lifetime
+    // of the `Uninit` isn't exposed.
+    let uninit = unsafe { Uninit::from_ptr(ptr) };
+    // SAFETY: The memory is leaked, so it's never reused.
+    let init = unsafe { uninit.pin_init_with(f) };
+    mem::forget(init);
+    // SAFETY: The value has been initialized and is never moved, since only
+    // a shared reference to it is returned.
+    unsafe { &*ptr }
+}
+
+#[test]
+fn uninit_pin_init_with() {
+    let drops = Cell::new(0);
+    let leaked = leak_pinned(self_ref(4, &drops));
+    assert_eq!(leaked.value, 4);
+    assert!(ptr::eq(leaked.this.as_ptr(), leaked));
+    assert_eq!(drops.get(), 0);
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "alloc")]
+
+use edile::{pin_proj_fn, AllocBox, AllocError, ArcExt, BoxExt, Global, RawAlloc, RcExt};
+use edile_derive::PinProjConstruct;
+
+use std::alloc::Layout;
+use std::cell::Cell;
+use std::marker::PhantomPinned;
+use std::mem;
+use std::ptr::NonNull;
+use std::rc::Rc;
+use std::sync::Arc;
+
+mod common;
+
+use common::{leaked_by_panic, CountingGlobal, BIG, LIVE};
+
+#[global_allocator]
+static GLOBAL: CountingGlobal = CountingGlobal;
+
+struct Pinned {
+    _payload: [u8; BIG],
+    _pin: PhantomPinned,
+}
+
+#[derive(PinProjConstruct)]
+struct Node {
+    #[pin]
+    pinned: Pinned,
+    id: u32,
+}
+
+/// Pins and forgets the `pinned` field of a `Node`, then panics.
+macro_rules! forget_and_panic {
+    () => {
+        pin_proj_fn::<Node, _>(|_, proj| {
+            mem::forget(proj.pinned.init(Pinned {
+                _payload: [0; BIG],
+                _pin: PhantomPinned,
+            }));
+            panic!("constructor failed after pinning a field")
+        })
+    };
+}
+
+#[test]
+fn box_pin_with_panic_leaks_memory() {
+    let leaked = leaked_by_panic(|| {
+        Box::pin_with(forget_and_panic!());
+    });
+    assert_eq!(leaked, 1);
+}
+
+#[test]
+fn rc_pin_with_panic_leaks_memory() {
+    let leaked = leaked_by_panic(|| {
+        Rc::pin_with(forget_and_panic!());
+    });
+    assert_eq!(leaked, 1);
+}
+
+#[test]
+fn arc_pin_with_panic_leaks_memory() {
+    let leaked = leaked_by_panic(|| {
+        Arc::pin_with(forget_and_panic!());
+    });
+    assert_eq!(leaked, 1);
+}
+
+/// An allocator that records whether it has been dropped. The flag is `'static`
+/// since pinning an `AllocBox` requires a `'static` allocator.
+struct DropFlag(&'static Cell<bool>);
+
+unsafe impl RawAlloc for DropFlag {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<u8>, AllocError> {
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        unsafe { Global.deallocate(ptr, layout) }
+    }
+}
+
+impl Drop for DropFlag {
+    fn drop(&mut self) {
+        self.0.set(true);
+    }
+}
+
+#[test]
+fn alloc_box_pin_in_with_panic_leaks_memory_and_allocator() {
+    let dropped: &'static Cell<bool> = Box::leak(Box::new(Cell::new(false)));
+    let leaked = leaked_by_panic(|| {
+        AllocBox::pin_in_with(DropFlag(dropped), forget_and_panic!());
+    });
+    assert_eq!(leaked, 1);
+    assert!(!dropped.get());
+}
+
+#[test]
+fn pin_with_frees_memory_when_dropped() {
+    let live = LIVE.with(Cell::get);
+    let node = Box::pin_with(pin_proj_fn::<Node, _>(|_, proj| NodePinInitProj {
+        pinned: proj.pinned.init(Pinned {
+            _payload: [0; BIG],
+            _pin: PhantomPinned,
+        }),
+        id: proj.id.init(1),
+    }));
+    assert_eq!((LIVE.with(Cell::get), node.id), (live + 1, 1));
+    drop(node);
+    assert_eq!(LIVE.with(Cell::get), live);
+
+    let dropped: &'static Cell<bool> = Box::leak(Box::new(Cell::new(false)));
+    let node = AllocBox::pin_in_with(
+        DropFlag(dropped),
+        pin_proj_fn::<Node, _>(|_, proj| NodePinInitProj {
+            pinned: proj.pinned.init(Pinned {
+                _payload: [0; BIG],
+                _pin: PhantomPinned,
+            }),
+            id: proj.id.init(2),
+        }),
+    );
+    assert_eq!(node.id, 2);
+    drop(node);
+    assert_eq!(LIVE.with(Cell::get), live);
+    assert!(dropped.get());
+}
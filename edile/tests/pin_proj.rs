@@ -0,0 +1,72 @@
+#![cfg(feature = "alloc")]
+
+use edile::project::PinProjMut;
+use edile::{pin_proj_fn, BoxExt, Own};
+use edile_derive::PinProjConstruct;
+
+use std::marker::PhantomPinned;
+use std::mem::MaybeUninit;
+use std::pin::Pin;
+
+struct Pinned {
+    value: u32,
+    _pin: PhantomPinned,
+}
+
+impl Pinned {
+    fn bump(self: Pin<&mut Self>) {
+        // SAFETY: `value` is not structurally pinned.
+        unsafe { self.get_unchecked_mut().value += 1 };
+    }
+}
+
+#[derive(PinProjConstruct)]
+struct Node {
+    #[pin]
+    pinned: Pinned,
+    count: u32,
+}
+
+#[test]
+fn derived_struct() {
+    let mut node = Box::pin_with(pin_proj_fn::<Node, _>(|_, proj| NodePinInitProj {
+        pinned: proj.pinned.init(Pinned {
+            value: 1,
+            _pin: PhantomPinned,
+        }),
+        count: proj.count.init(10),
+    }));
+    let proj: NodePinMutProj<'_> = node.as_mut().project_pin();
+    proj.pinned.bump();
+    *proj.count += 1;
+    assert_eq!(node.pinned.value, 2);
+    assert_eq!(node.count, 11);
+}
+
+#[test]
+fn pinned_own_tuple() {
+    let mut storage = MaybeUninit::uninit();
+    let pinned = Pinned {
+        value: 5,
+        _pin: PhantomPinned,
+    };
+    let own = Own::new_with(&mut storage, |uninit| {
+        uninit.init((pinned, String::from("a")))
+    });
+    // SAFETY: `own` is dropped at the end of the test, before `storage`.
+    let mut own = unsafe { Own::into_pin(own) };
+    let (pinned, mut string) = own.as_mut().project_pin();
+    pinned.bump();
+    string.push('b');
+    assert_eq!(own.0.value, 6);
+    assert_eq!(own.1, "ab");
+}
+
+#[test]
+fn pinned_array() {
+    let mut array = Box::pin([1, 2, 3]);
+    for elem in array.as_mut().project_pin() {
+        *elem.get_mut() *= 2;
+    }
+    assert_eq!(*array, [2, 4, 6]);
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "alloc")]
+
+use edile::{pin_proj_fn, BoxExt, PinnedDrop, PinnedDropToken};
+use edile_derive::PinProjConstruct;
+
+use std::cell::Cell;
+use std::marker::PhantomPinned;
+use std::pin::Pin;
+
+struct Registration<'a> {
+    registered: &'a Cell<Option<*const Registration<'a>>>,
+    _pin: PhantomPinned,
+}
+
+#[derive(PinProjConstruct)]
+#[pin(PinnedDrop)]
+struct Registered<'a> {
+    #[pin]
+    registration: Registration<'a>,
+    drops: &'a Cell<usize>,
+}
+
+impl PinnedDrop for Registered<'_> {
+    fn drop(self: Pin<&mut Self>, _: PinnedDropToken) {
+        let registration = &self.registration;
+        assert_eq!(
+            registration.registered.get(),
+            Some(registration as *const _)
+        );
+        registration.registered.set(None);
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+#[test]
+fn pinned_drop_is_called_once() {
+    let registered = Cell::new(None);
+    let drops = Cell::new(0);
+    let value = Box::pin_with(pin_proj_fn::<Registered, _>(|_, proj| {
+        registered.set(Some(proj.registration.addr().as_ptr() as *const _));
+        RegisteredPinInitProj {
+            registration: proj.registration.init(Registration {
+                registered: &registered,
+                _pin: PhantomPinned,
+            }),
+            drops: proj.drops.init(&drops),
+        }
+    }));
+    assert!(registered.get().is_some());
+    drop(value);
+    assert_eq!(registered.get(), None);
+    assert_eq!(drops.get(), 1);
+}
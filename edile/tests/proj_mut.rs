@@ -0,0 +1,111 @@
+use edile::project::ProjMut;
+use edile::{local, proj_fn, Own};
+use edile_derive::{ProjConstruct, ProjMut};
+
+use std::mem::{self, MaybeUninit};
+
+#[derive(ProjMut)]
+struct Point {
+    x: u32,
+    y: u32,
+}
+
+#[derive(ProjMut)]
+struct Wrapper<'s, T>(&'s str, T);
+
+#[derive(ProjMut)]
+struct Unit;
+
+#[derive(ProjMut, Debug, PartialEq)]
+enum Shape {
+    Circle { radius: u32 },
+    Rect(u32, u32),
+    Empty,
+}
+
+#[derive(ProjConstruct, ProjMut)]
+struct Both {
+    name: String,
+    len: usize,
+}
+
+#[test]
+fn tuple_fields() {
+    let mut tuple = (1u8, String::from("a"), [0u16; 2]);
+    let (a, b, c) = tuple.project_mut();
+    *a += 1;
+    b.push('b');
+    c[1] = *a as u16;
+    assert_eq!(tuple, (2, String::from("ab"), [0, 2]));
+}
+
+#[test]
+fn array_elements() {
+    let mut array = [1, 2, 3];
+    let [first, .., last] = array.project_mut();
+    mem::swap(first, last);
+    assert_eq!(array, [3, 2, 1]);
+}
+
+#[test]
+fn struct_fields() {
+    let mut point = Point { x: 1, y: 2 };
+    let proj: PointMutProj<'_> = point.project_mut();
+    mem::swap(proj.x, proj.y);
+    assert_eq!((point.x, point.y), (2, 1));
+
+    let mut wrapper = Wrapper("wrapper", vec![1]);
+    let WrapperMutProj(name, vec) = wrapper.project_mut();
+    vec.push(name.len());
+    *name = "changed";
+    assert_eq!((wrapper.0, &*wrapper.1), ("changed", &[1, 7][..]));
+}
+
+#[test]
+fn fieldless_struct() {
+    let () = Unit.project_mut();
+}
+
+#[test]
+fn enum_variants() {
+    let mut shapes = [Shape::Circle { radius: 1 }, Shape::Rect(2, 3), Shape::Empty];
+    for shape in &mut shapes {
+        match shape.project_mut() {
+            ShapeMutProj::Circle { radius } => *radius *= 10,
+            ShapeMutProj::Rect(w, h) => mem::swap(w, h),
+            ShapeMutProj::Empty => {}
+        }
+    }
+    assert_eq!(
+        shapes,
+        [
+            Shape::Circle { radius: 10 },
+            Shape::Rect(3, 2),
+            Shape::Empty
+        ]
+    );
+}
+
+#[test]
+fn through_init() {
+    local!(mut both: Both = with_proj proj => BothInitProj {
+        name: proj.name.init("both".into()),
+        len: proj.len.init(0),
+    });
+    let proj = both.project_mut();
+    *proj.len = proj.name.len();
+    assert_eq!(both.len, 4);
+
+    let mut storage = MaybeUninit::uninit();
+    let mut both = Own::new_with(
+        &mut storage,
+        proj_fn::<Both, _>(|_, proj| BothInitProj {
+            name: proj.name.init(String::new()),
+            len: proj.len.init(0),
+        }),
+    );
+    let BothMutProj { name, len } = both.project_mut();
+    name.push_str("own");
+    *len = name.len();
+    assert_eq!((&*both.name, both.len), ("own", 3));
+}
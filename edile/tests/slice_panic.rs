@@ -0,0 +1,86 @@
+#![cfg(feature = "alloc")]
+
+use edile::{slice_each, ArcSliceExt, BoxSliceExt, RcSliceExt};
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::Arc;
+
+mod common;
+
+use common::{leaked_by_panic, CountingGlobal, BIG, LIVE};
+
+#[global_allocator]
+static GLOBAL: CountingGlobal = CountingGlobal;
+
+/// An element big enough for slices of it to be tracked by `CountingGlobal`.
+struct Element<'a> {
+    drops: &'a Cell<usize>,
+    _payload: [u8; BIG],
+}
+
+impl Drop for Element<'_> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+/// Calls `new_slice_with` with a constructor initializing 2 of 4 elements and then
+/// panicking, checking the initialized elements are dropped and the memory is freed.
+fn check_panic_halfway(new_slice_with: impl FnOnce(&Cell<usize>)) {
+    let drops = Cell::new(0);
+    assert_eq!(leaked_by_panic(|| new_slice_with(&drops)), 0);
+    assert_eq!(drops.get(), 2);
+}
+
+macro_rules! halfway {
+    ($drops:expr) => {
+        slice_each(|idx, uninit| match idx {
+            2 => panic!("element {} failed", idx),
+            _ => uninit.init(Element {
+                drops: $drops,
+                _payload: [0; BIG],
+            }),
+        })
+    };
+}
+
+#[test]
+fn box_new_slice_with_panic() {
+    check_panic_halfway(|drops| {
+        Box::<[Element]>::new_slice_with(4, halfway!(drops));
+    });
+}
+
+#[test]
+fn rc_new_slice_with_panic() {
+    check_panic_halfway(|drops| {
+        Rc::<[Element]>::new_slice_with(4, halfway!(drops));
+    });
+}
+
+#[test]
+fn arc_new_slice_with_panic() {
+    check_panic_halfway(|drops| {
+        Arc::<[Element]>::new_slice_with(4, halfway!(drops));
+    });
+}
+
+#[test]
+fn new_slice_with_frees_on_success() {
+    let drops = Cell::new(0);
+    let live = LIVE.with(Cell::get);
+    let boxed = Box::<[Element]>::new_slice_with(
+        3,
+        slice_each(|_, uninit| {
+            uninit.init(Element {
+                drops: &drops,
+                _payload: [0; BIG],
+            })
+        }),
+    );
+    assert_eq!(LIVE.with(Cell::get), live + 1);
+    drop(boxed);
+    assert_eq!(drops.get(), 3);
+    assert_eq!(LIVE.with(Cell::get), live);
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "alloc")]
+
+use edile::{pin_proj_fn, BoxExt, PinUninit, StableAddr};
+use edile_derive::PinProjConstruct;
+
+use std::marker::PhantomPinned;
+use std::ptr;
+
+struct Header {
+    id: u32,
+    _pin: PhantomPinned,
+}
+
+struct Link {
+    header: *const Header,
+    node: StableAddr<Node>,
+}
+
+#[derive(PinProjConstruct)]
+struct Node {
+    #[pin]
+    header: Header,
+    list_link: Link,
+}
+
+#[test]
+fn node_links_to_its_header() {
+    let node = Box::pin_with(|uninit: PinUninit<'_, Node>| {
+        let node = uninit.addr();
+        uninit.init_with(pin_proj_fn::<Node, _>(move |_, proj| {
+            let header = proj.header.addr().as_ptr();
+            NodePinInitProj {
+                header: proj.header.init(Header {
+                    id: 7,
+                    _pin: PhantomPinned,
+                }),
+                list_link: proj.list_link.init(Link { header, node }),
+            }
+        }))
+    });
+    assert!(ptr::eq(node.list_link.header, &node.header));
+    assert!(ptr::eq(node.list_link.node.as_ptr(), &*node));
+    // SAFETY: The node is alive and pinned, so the header is still at that address.
+    assert_eq!(unsafe { (*node.list_link.header).id }, 7);
+}
+
+#[test]
+fn addr_is_comparable() {
+    let node = Box::pin_with(|uninit: PinUninit<'_, u32>| {
+        let first = uninit.addr();
+        assert_eq!(first, uninit.addr());
+        uninit.init(3)
+    });
+    assert_eq!(*node, 3);
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "alloc")]
+
+use edile::{slice_each, AllocBox, AllocError, BoxExt, BoxSliceExt, Global, RawAlloc, Uninit};
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::ptr::NonNull;
+
+/// A global allocator that fails when `FAIL` is set on the current thread.
+struct FailingGlobal;
+
+thread_local! {
+    static FAIL: Cell<bool> = const { Cell::new(false) };
+}
+
+unsafe impl GlobalAlloc for FailingGlobal {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        match FAIL.try_with(Cell::get) {
+            Ok(true) => std::ptr::null_mut(),
+            _ => unsafe { System.alloc(layout) },
+        }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: FailingGlobal = FailingGlobal;
+
+/// Runs `f` while the global allocator fails.
+fn failing<R>(f: impl FnOnce() -> R) -> R {
+    FAIL.with(|fail| fail.set(true));
+    let result = f();
+    FAIL.with(|fail| fail.set(false));
+    result
+}
+
+/// A `RawAlloc` that always fails.
+struct FailingAlloc;
+
+unsafe impl RawAlloc for FailingAlloc {
+    fn allocate(&self, _: Layout) -> Result<NonNull<u8>, AllocError> {
+        Err(AllocError)
+    }
+
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {
+        unreachable!("nothing was allocated")
+    }
+}
+
+fn unreachable_ctor<T: ?Sized>(_: Uninit<'_, T>) -> edile::Init<'_, T> {
+    unreachable!("the constructor must not be called")
+}
+
+#[test]
+fn box_try_new_with() {
+    let boxed = Box::try_new_with(|uninit| uninit.init([1u64; 32])).unwrap();
+    assert_eq!(*boxed, [1; 32]);
+
+    let result = failing(|| Box::<[u64; 32]>::try_new_with(unreachable_ctor));
+    assert_eq!(result.err(), Some(AllocError));
+}
+
+#[test]
+fn box_try_new_with_zero_sized() {
+    let boxed = failing(|| Box::try_new_with(|uninit| uninit.init(())));
+    assert_eq!(boxed.ok().as_deref(), Some(&()));
+}
+
+#[test]
+fn box_try_new_slice_with() {
+    let boxed = Box::<[u32]>::try_new_slice_with(4, slice_each(|i, uninit| uninit.init(i as u32)));
+    assert_eq!(&*boxed.unwrap(), &[0, 1, 2, 3]);
+
+    let result = failing(|| Box::<[u32]>::try_new_slice_with(4, unreachable_ctor));
+    assert_eq!(result.err(), Some(AllocError));
+
+    let result = Box::<[u32]>::try_new_slice_with(usize::MAX, unreachable_ctor);
+    assert_eq!(result.err(), Some(AllocError));
+}
+
+#[test]
+fn alloc_box_try_new_in_with() {
+    let boxed = AllocBox::try_new_in_with(Global, |uninit| uninit.init(String::from("a")));
+    assert_eq!(*boxed.unwrap(), "a");
+
+    let result = AllocBox::<String, _>::try_new_in_with(FailingAlloc, unreachable_ctor);
+    assert_eq!(result.err(), Some(AllocError));
+
+    let result = failing(|| AllocBox::<String, _>::try_new_in_with(Global, unreachable_ctor));
+    assert_eq!(result.err(), Some(AllocError));
+}
+
+#[test]
+fn alloc_box_try_new_slice_in_with() {
+    let result = AllocBox::<[u8], _>::try_new_slice_in_with(8, FailingAlloc, unreachable_ctor);
+    assert_eq!(result.err(), Some(AllocError));
+
+    let result = AllocBox::<[u64], _>::try_new_slice_in_with(usize::MAX, Global, unreachable_ctor);
+    assert_eq!(result.err(), Some(AllocError));
+}
+
+#[cfg(feature = "nightly")]
+#[test]
+fn rc_try_new_with() {
+    use edile::RcExt;
+    use std::rc::Rc;
+
+    let rc = Rc::try_new_with(|uninit| uninit.init([1u64; 32])).unwrap();
+    assert_eq!(*rc, [1; 32]);
+
+    let result = failing(|| Rc::<[u64; 32]>::try_new_with(unreachable_ctor));
+    assert_eq!(result.err(), Some(AllocError));
+}
+
+#[cfg(feature = "nightly")]
+#[test]
+fn arc_try_new_with() {
+    use edile::ArcExt;
+    use std::sync::Arc;
+
+    let arc = Arc::try_new_with(|uninit| uninit.init([1u64; 32])).unwrap();
+    assert_eq!(*arc, [1; 32]);
+
+    let result = failing(|| Arc::<[u64; 32]>::try_new_with(unreachable_ctor));
+    assert_eq!(result.err(), Some(AllocError));
+}
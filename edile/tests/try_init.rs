@@ -0,0 +1,136 @@
+use edile::{try_array_each, try_ctor, try_proj_fn, try_slice_each, Init, Own, Uninit};
+use edile_derive::ProjConstruct;
+
+use std::cell::Cell;
+use std::mem::MaybeUninit;
+use std::num::ParseIntError;
+use std::ptr;
+
+mod common;
+
+use common::DropCounter;
+
+#[derive(ProjConstruct)]
+struct Config<'a> {
+    counter: DropCounter<'a>,
+    port: u32,
+}
+
+fn parse(s: &'static str) -> try_ctor!(u32, ParseIntError) {
+    move |uninit| Ok(uninit.init(s.parse()?))
+}
+
+#[test]
+fn try_init_with_returns_error() {
+    let mut storage = MaybeUninit::<u32>::uninit();
+    let addr = storage.as_ptr();
+    let res = Own::try_new_with(&mut storage, |uninit| uninit.try_init_with(parse("nope")));
+    assert!(res.err().is_some());
+
+    let res = Own::try_new_with(&mut storage, |uninit| uninit.try_init_with(parse("42")));
+    let own = res.unwrap();
+    assert_eq!(*own, 42);
+    assert!(ptr::eq(&*own, addr));
+}
+
+#[test]
+fn own_storage_is_reusable_on_error() {
+    let mut storage = MaybeUninit::<String>::uninit();
+    let res = Own::try_new_with(&mut storage, |_| Err("failed"));
+    assert_eq!(res.err(), Some("failed"));
+
+    let own = Own::try_new_with(&mut storage, |uninit| {
+        Ok::<_, ()>(uninit.init(String::from("reused")))
+    });
+    assert_eq!(*own.unwrap(), "reused");
+}
+
+#[test]
+fn try_proj_fn_drops_initialized_fields() {
+    let drops = Cell::new(0);
+    let mut storage = MaybeUninit::<(DropCounter, u32, DropCounter)>::uninit();
+    let res = Own::try_new_with(
+        &mut storage,
+        try_proj_fn::<(DropCounter, u32, DropCounter), _, ParseIntError>(
+            |_, (counter, port, unreached)| {
+                let counter = counter.init(DropCounter(&drops));
+                let port = port.try_init_with(parse("nope"))?;
+                let unreached = unreached.init(DropCounter(&drops));
+                Ok((counter, port, unreached))
+            },
+        ),
+    );
+    assert!(res.is_err());
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn try_proj_fn_derived_struct() {
+    fn config<'c>(
+        drops: &'c Cell<usize>,
+        port: &'static str,
+    ) -> try_ctor!(Config<'c>, ParseIntError) {
+        try_proj_fn(move |_, uninit: ConfigUninitProj<'_, 'c>| {
+            let counter = uninit.counter.init(DropCounter(drops));
+            let port = uninit.port.try_init_with(parse(port))?;
+            Ok(ConfigInitProj { counter, port })
+        })
+    }
+
+    let drops = Cell::new(0);
+    let mut storage = MaybeUninit::uninit();
+    assert!(Own::try_new_with(&mut storage, config(&drops, "nope")).is_err());
+    assert_eq!(drops.get(), 1);
+
+    let own = Own::try_new_with(&mut storage, config(&drops, "8080")).unwrap();
+    assert_eq!(own.port, 8080);
+    drop(own);
+    assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn try_proj_fn_array() {
+    let mut storage = MaybeUninit::<[u32; 3]>::uninit();
+    let own = Own::try_new_with(
+        &mut storage,
+        try_proj_fn(|_, [a, b, c]: [Uninit<'_, u32>; 3]| {
+            let a: Init<'_, u32> = a.try_init_with(parse("1"))?;
+            let b = b.try_init_with(parse("2"))?;
+            let c = c.try_init_with(parse("3"))?;
+            Ok::<_, ParseIntError>([a, b, c])
+        }),
+    );
+    assert_eq!(*own.unwrap(), [1, 2, 3]);
+}
+
+#[test]
+fn try_slice_each_drops_prefix() {
+    let drops = Cell::new(0);
+    let mut storage = [(); 4].map(|_| MaybeUninit::uninit());
+    let res = Own::<[DropCounter]>::try_new_with(
+        &mut storage[..],
+        try_slice_each(|idx, uninit| match idx {
+            2 => Err("bad element"),
+            _ => Ok(uninit.init(DropCounter(&drops))),
+        }),
+    );
+    assert_eq!(res.err(), Some((2, "bad element")));
+    assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn try_array_each_parses_records() {
+    let records = ["1", "2", "x", "4"];
+    let mut storage = MaybeUninit::<[u32; 4]>::uninit();
+    let res = Own::try_new_with(
+        &mut storage,
+        try_array_each(|idx, uninit| uninit.try_init_with(parse(records[idx]))),
+    );
+    assert_eq!(res.err().map(|(idx, _)| idx), Some(2));
+
+    let own = Own::try_new_with(
+        &mut storage,
+        try_array_each(|idx, uninit| uninit.try_init_with(parse(records[idx % 2]))),
+    );
+    assert_eq!(*own.unwrap(), [1, 2, 1, 2]);
+}
@@ -0,0 +1,111 @@
+#![cfg(feature = "alloc")]
+
+use edile::VecExt;
+
+use std::cell::Cell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+struct Tracked<'a> {
+    id: u32,
+    drops: &'a Cell<usize>,
+}
+
+impl Drop for Tracked<'_> {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+fn tracked<'a>(drops: &'a Cell<usize>, ids: &[u32]) -> Vec<Tracked<'a>> {
+    ids.iter().map(|&id| Tracked { id, drops }).collect()
+}
+
+fn ids(vec: &[Tracked]) -> Vec<u32> {
+    vec.iter().map(|t| t.id).collect()
+}
+
+#[test]
+fn push_with_panic_leaves_vec_unchanged() {
+    let drops = Cell::new(0);
+    let mut vec = tracked(&drops, &[1, 2]);
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        vec.push_with(|_| panic!("push failed"));
+    }));
+    assert!(res.is_err());
+    assert_eq!(ids(&vec), [1, 2]);
+    assert_eq!(drops.get(), 0);
+
+    vec.push_with(|uninit| {
+        uninit.init(Tracked {
+            id: 3,
+            drops: &drops,
+        })
+    });
+    assert_eq!(ids(&vec), [1, 2, 3]);
+    drop(vec);
+    assert_eq!(drops.get(), 3);
+}
+
+#[test]
+fn insert_with_panic_shifts_tail_back() {
+    let drops = Cell::new(0);
+    let mut vec = tracked(&drops, &[1, 2, 3, 4]);
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        vec.insert_with(1, |_| panic!("insert failed"));
+    }));
+    assert!(res.is_err());
+    assert_eq!(vec.len(), 4);
+    assert_eq!(ids(&vec), [1, 2, 3, 4]);
+    assert_eq!(drops.get(), 0);
+
+    vec.insert_with(1, |uninit| {
+        uninit.init(Tracked {
+            id: 5,
+            drops: &drops,
+        })
+    });
+    assert_eq!(ids(&vec), [1, 5, 2, 3, 4]);
+    drop(vec);
+    assert_eq!(drops.get(), 5);
+}
+
+#[test]
+fn insert_with_panic_at_end() {
+    let drops = Cell::new(0);
+    let mut vec = tracked(&drops, &[1, 2]);
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        vec.insert_with(2, |_| panic!("insert failed"));
+    }));
+    assert!(res.is_err());
+    assert_eq!(ids(&vec), [1, 2]);
+    drop(vec);
+    assert_eq!(drops.get(), 2);
+}
+
+#[test]
+fn extend_with_panic_drops_initialized_prefix() {
+    let drops = Cell::new(0);
+    let mut vec = tracked(&drops, &[1]);
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        vec.extend_with(4, |idx, uninit| match idx {
+            2 => panic!("element {} failed", idx),
+            _ => uninit.init(Tracked {
+                id: 10 + idx as u32,
+                drops: &drops,
+            }),
+        });
+    }));
+    assert!(res.is_err());
+    assert_eq!(drops.get(), 2);
+    assert_eq!(ids(&vec), [1]);
+
+    vec.extend_with(2, |idx, uninit| {
+        uninit.init(Tracked {
+            id: 20 + idx as u32,
+            drops: &drops,
+        })
+    });
+    assert_eq!(ids(&vec), [1, 20, 21]);
+    drop(vec);
+    assert_eq!(drops.get(), 5);
+}